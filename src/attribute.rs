@@ -205,6 +205,15 @@ impl Attribute {
         self
     }
 
+    /// zero-initializes the attribute, using `{0}` in C and `{}` in C++
+    ///
+    /// # Example
+    ///
+    /// `struct foo x` => `struct foo x = {0};`
+    pub fn zero_init(&mut self) -> &mut Self {
+        self.set_value(Expr::zero_init())
+    }
+
     /// obtains a reference to the initializer value for the attribute
     pub fn value(&self) -> Option<&Expr> {
         self.value.as_ref()
@@ -220,8 +229,7 @@ impl Attribute {
             write!(fmt, "static ")?;
         }
 
-        self.ty.fmt(fmt)?;
-        write!(fmt, " {}", self.name)?;
+        self.ty.fmt_with_name(fmt, &self.name)?;
 
         if self.ty.is_array() {
             write!(fmt, "[{}]", self.ty.get_array_size())?;
@@ -248,8 +256,7 @@ impl Attribute {
             write!(fmt, "static ")?;
         }
 
-        self.ty.fmt(fmt)?;
-        write!(fmt, " {}", self.name)?;
+        self.ty.fmt_with_name(fmt, &self.name)?;
 
         if self.ty.is_array() {
             write!(fmt, "[{}]", self.ty.get_array_size())?;
@@ -260,7 +267,8 @@ impl Attribute {
         }
 
         if let Some(v) = &self.value {
-            write!(fmt, " = {v}")?;
+            write!(fmt, " = ")?;
+            v.fmt(fmt)?;
         }
 
         writeln!(fmt, ";")
@@ -276,8 +284,7 @@ impl Attribute {
             write!(fmt, "static ")?;
         }
 
-        self.ty.fmt(fmt)?;
-        write!(fmt, " {}", self.name)?;
+        self.ty.fmt_with_name(fmt, &self.name)?;
         if self.ty.is_array() {
             write!(fmt, "[{}]", self.ty.get_array_size())?;
         }
@@ -287,7 +294,8 @@ impl Attribute {
 
         if let Some(v) = &self.value {
             if !decl_only {
-                write!(fmt, " = {v}")?;
+                write!(fmt, " = ")?;
+                v.fmt(fmt)?;
             }
         }
         writeln!(fmt, ";")
@@ -0,0 +1,161 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2022 Reto Achermann
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Attribute Specifiers
+//!
+//! This module defines compiler attributes of functions and methods. An attribute
+//! is emitted as `[[gnu::hot]]` in C++. In C, GNU attributes are emitted as
+//! `__attribute__((hot))`, and so are the standard attributes with a GNU equivalent,
+//! e.g., `[[nodiscard]]` as `__attribute__((warn_unused_result))`. Other attributes
+//! require C23 and are omitted.
+
+// std includes
+use std::fmt::{self, Display, Write};
+
+use crate::{Dialect, Formatter};
+
+/// defines a compiler attribute, e.g., `[[gnu::hot]]` or `__attribute__((hot))`
+#[derive(Debug, Clone)]
+pub struct AttributeSpec {
+    /// the namespace of the attribute, e.g., `gnu`
+    namespace: Option<String>,
+    /// the name of the attribute
+    name: String,
+    /// the arguments of the attribute
+    args: Vec<String>,
+}
+
+impl AttributeSpec {
+    /// creates a new standard attribute without a namespace, e.g., `[[nodiscard]]`
+    ///
+    /// In C, the standard attributes `nodiscard`, `deprecated`, `maybe_unused`,
+    /// `noreturn`, and `fallthrough` use their GNU spelling, other ones are omitted.
+    pub fn new(name: &str) -> Self {
+        AttributeSpec {
+            namespace: None,
+            name: String::from(name),
+            args: Vec::new(),
+        }
+    }
+
+    /// creates a new attribute with the given namespace, e.g., `[[clang::name]]`
+    pub fn with_namespace(namespace: &str, name: &str) -> Self {
+        AttributeSpec {
+            namespace: Some(String::from(namespace)),
+            name: String::from(name),
+            args: Vec::new(),
+        }
+    }
+
+    /// creates a new GNU attribute, e.g., `[[gnu::hot]]` or `__attribute__((hot))`
+    pub fn new_gnu(name: &str) -> Self {
+        Self::with_namespace("gnu", name)
+    }
+
+    /// adds an argument to the attribute
+    ///
+    /// # Example
+    ///
+    /// `[[gnu::aligned]]` => `[[gnu::aligned(16)]]`
+    pub fn push_arg(&mut self, arg: &str) -> &mut Self {
+        self.args.push(String::from(arg));
+        self
+    }
+
+    /// obtains the name of the attribute
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// obtains the namespace of the attribute
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// formats the attribute name with its arguments
+    ///
+    /// The arguments are dropped if the name is respelled, e.g., the reason of
+    /// `[[nodiscard("reason")]]` has no place in `__attribute__((warn_unused_result))`.
+    fn fmt_attr(&self, fmt: &mut Formatter<'_>, name: &str) -> fmt::Result {
+        write!(fmt, "{name}")?;
+        if !self.args.is_empty() && name == self.name {
+            write!(fmt, "({})", self.args.join(", "))?;
+        }
+        Ok(())
+    }
+
+    /// obtains the name of the attribute in the `__attribute__` syntax, if any
+    ///
+    /// # Example
+    ///
+    /// `[[gnu::hot]]` => `hot`, `[[nodiscard]]` => `warn_unused_result`
+    fn gnu_name(&self) -> Option<&str> {
+        match (self.namespace.as_deref(), self.name.as_str()) {
+            (Some("gnu"), name) => Some(name),
+            (None, "nodiscard") => Some("warn_unused_result"),
+            (None, "maybe_unused") => Some("unused"),
+            (None, name @ ("deprecated" | "noreturn" | "fallthrough")) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// tests whether the attribute can be emitted in the given dialect
+    ///
+    /// The `[[...]]` syntax requires C23 in C, thus only attributes with a GNU spelling
+    /// are supported.
+    pub fn is_supported(&self, dialect: Dialect) -> bool {
+        dialect.is_cpp() || self.gnu_name().is_some()
+    }
+
+    /// formats the attribute, using the `__attribute__` syntax for GNU attributes in C
+    ///
+    /// Nothing is emitted for attributes that are not supported in the dialect.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if !self.is_supported(fmt.dialect()) {
+            return Ok(());
+        }
+
+        if let (false, Some(name)) = (fmt.dialect().is_cpp(), self.gnu_name()) {
+            write!(fmt, "__attribute__((")?;
+            self.fmt_attr(fmt, name)?;
+            return write!(fmt, "))");
+        }
+
+        write!(fmt, "[[")?;
+        if let Some(ns) = &self.namespace {
+            write!(fmt, "{ns}::")?;
+        }
+        self.fmt_attr(fmt, &self.name)?;
+        write!(fmt, "]]")
+    }
+}
+
+impl Display for AttributeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ret = String::new();
+        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        write!(f, "{ret}")
+    }
+}
@@ -30,8 +30,10 @@
 // std includes
 use std::fmt::{self, Write};
 
+use crate::expr::{add_include, fn_call_include};
 use crate::{
-    Comment, DoWhileLoop, Expr, ForLoop, Formatter, IfElse, Switch, Type, Variable, WhileLoop,
+    Comment, Dialect, DoWhileLoop, Expr, ForLoop, Formatter, IfElse, RangeForLoop, Switch,
+    TryCatch, Type, Variable, WhileLoop,
 };
 
 /// defines an item of the scope
@@ -41,20 +43,29 @@ enum Item {
     Variable(Variable),
     IfElse(IfElse),
     ForLoop(ForLoop),
+    RangeForLoop(RangeForLoop),
     WhileLoop(WhileLoop),
     DoWhileLoop(DoWhileLoop),
     Return(Option<Expr>),
+    Throw(Option<Expr>),
     Assign(Expr, Expr),
+    CompoundAssign(Expr, String, Expr),
+    Increment(Expr),
+    Decrement(Expr),
     GoTo(String),
     Label(String),
     Raw(String),
+    RawNoSemi(String),
     Expr(Expr),
     FnCall(String, Vec<Expr>),
     MethodCall(Expr, String, Vec<Expr>),
+    Assert(Expr),
     Break,
     Continue,
+    Null,
     NewLine,
     Switch(Switch),
+    TryCatch(TryCatch),
 }
 
 /// defines the scope of the generated C code
@@ -81,6 +92,33 @@ impl Block {
         self.items.is_empty()
     }
 
+    /// returns the number of statements in the block, including nested blocks
+    ///
+    /// Comments and empty lines are not counted. Compound statements such as loops
+    /// count as one statement plus the statements in their bodies.
+    ///
+    /// # Example
+    ///
+    /// `x = 1; if (c) { y = 2; }` => 3
+    pub fn stmt_count(&self) -> usize {
+        let mut count = 0;
+        for item in self.items.iter() {
+            let nested = match item {
+                Item::Comment(_) | Item::NewLine => continue,
+                Item::IfElse(v) => v.blocks(),
+                Item::Switch(v) => v.blocks(),
+                Item::TryCatch(v) => v.blocks(),
+                Item::ForLoop(v) => v.blocks(),
+                Item::RangeForLoop(v) => v.blocks(),
+                Item::WhileLoop(v) => v.blocks(),
+                Item::DoWhileLoop(v) => v.blocks(),
+                _ => Vec::new(),
+            };
+            count += 1 + nested.iter().map(|b| b.stmt_count()).sum::<usize>();
+        }
+        count
+    }
+
     /// clears the body
     pub fn clear(&mut self) {
         self.items.clear();
@@ -92,6 +130,20 @@ impl Block {
         self
     }
 
+    /// adds a heading comment separating a section of the block
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// ////////////////////////
+    /// // cleanup
+    /// ////////////////////////
+    /// ```
+    pub fn section(&mut self, title: &str) -> &mut Self {
+        self.items.push(Item::Comment(Comment::new_heading(title)));
+        self
+    }
+
     /// adds a `break` statement to the block
     pub fn break_stmt(&mut self) -> &mut Self {
         self.items.push(Item::Break);
@@ -104,6 +156,18 @@ impl Block {
         self
     }
 
+    /// adds "raw" content to the block without a terminating semicolon, copying the string
+    ///
+    /// Multi-line content is indented line by line.
+    ///
+    /// # Example
+    ///
+    /// `#pragma unroll` => `#pragma unroll`
+    pub fn raw_no_semi(&mut self, raw: &str) -> &mut Self {
+        self.items.push(Item::RawNoSemi(String::from(raw)));
+        self
+    }
+
     /// pushs a "raw" statement to the block
     pub fn raw(&mut self, raw: String) -> &mut Self {
         self.items.push(Item::Raw(raw));
@@ -116,6 +180,39 @@ impl Block {
         self
     }
 
+    /// pushes a compound assignment operation to the block
+    ///
+    /// The operator may be given with or without the trailing `=`.
+    ///
+    /// # Example
+    ///
+    /// `compound_assign(sum, "+", x)` => `sum += x;`
+    pub fn compound_assign(&mut self, lhs: Expr, op: &str, rhs: Expr) -> &mut Self {
+        let op = op.strip_suffix('=').unwrap_or(op);
+        self.items.push(Item::CompoundAssign(lhs, String::from(op), rhs));
+        self
+    }
+
+    /// pushes an increment statement to the block
+    ///
+    /// # Example
+    ///
+    /// `increment(i)` => `i++;`
+    pub fn increment(&mut self, lhs: Expr) -> &mut Self {
+        self.items.push(Item::Increment(lhs));
+        self
+    }
+
+    /// pushes a decrement statement to the block
+    ///
+    /// # Example
+    ///
+    /// `decrement(i)` => `i--;`
+    pub fn decrement(&mut self, lhs: Expr) -> &mut Self {
+        self.items.push(Item::Decrement(lhs));
+        self
+    }
+
     /// adds a new label to the block
     pub fn label(&mut self, label: &str) -> &mut Self {
         self.items.push(Item::Label(String::from(label)));
@@ -128,6 +225,157 @@ impl Block {
         self
     }
 
+    /// breaks out of an enclosing loop by jumping to its exit label
+    ///
+    /// C has no labeled `break`, so leaving nested loops is lowered to a `goto` to the
+    /// label placed after the outer loop, see [`Block::new_for_loop_with_exit`].
+    ///
+    /// # Example
+    ///
+    /// `break_to_label("outer")` => `goto outer;`
+    pub fn break_to_label(&mut self, label: &str) -> &mut Self {
+        self.goto(label)
+    }
+
+    /// adds an exit label followed by a null statement, so it may end a block
+    ///
+    /// # Example
+    ///
+    /// `exit_label("outer")` => `outer:`, `;`
+    pub fn exit_label(&mut self, label: &str) -> &mut Self {
+        self.label(label);
+        self.items.push(Item::Null);
+        self
+    }
+
+    /// adds an assertion `assert(cond);` to the block
+    ///
+    /// The assertion requires `assert.h`, see [`Block::required_includes`].
+    pub fn assert(&mut self, cond: Expr) -> &mut Self {
+        self.items.push(Item::Assert(cond));
+        self
+    }
+
+    /// obtains the system includes required by the statements of this block and
+    /// all nested blocks in the given dialect
+    ///
+    /// # Example
+    ///
+    /// `assert(x);`  => `["assert.h"]` (C), `["cassert"]` (C++)
+    pub fn required_includes(&self, dialect: Dialect) -> Vec<&'static str> {
+        let mut incs = Vec::new();
+        self.collect_includes(dialect, &mut incs);
+        incs
+    }
+
+    /// obtains the types of the variables declared in this block and all nested blocks
+    pub(crate) fn used_types(&self) -> Vec<Type> {
+        let mut ret = Vec::new();
+        for item in self.items.iter() {
+            let nested = match item {
+                Item::Variable(v) => {
+                    ret.push(v.to_type());
+                    continue;
+                }
+                Item::IfElse(v) => v.blocks(),
+                Item::Switch(v) => v.blocks(),
+                Item::TryCatch(v) => v.blocks(),
+                Item::ForLoop(v) => v.blocks(),
+                Item::RangeForLoop(v) => v.blocks(),
+                Item::WhileLoop(v) => v.blocks(),
+                Item::DoWhileLoop(v) => v.blocks(),
+                _ => continue,
+            };
+            for b in nested {
+                ret.extend(b.used_types());
+            }
+        }
+        ret
+    }
+
+    /// checks for variables that are declared more than once in the same block
+    ///
+    /// Nested blocks are separate scopes and are checked on their own. Returns the
+    /// names of the redeclared variables, each reported once per block.
+    ///
+    /// # Example
+    ///
+    /// `int i; int j; int i;` => `["i"]`
+    pub fn check_declarations(&self) -> Vec<String> {
+        let mut ret = Vec::new();
+        let mut declared: Vec<&str> = Vec::new();
+        for item in self.items.iter() {
+            let nested = match item {
+                Item::Variable(v) => {
+                    if !declared.contains(&v.name()) {
+                        declared.push(v.name());
+                    } else if !ret.iter().any(|n| n == v.name()) {
+                        ret.push(String::from(v.name()));
+                    }
+                    continue;
+                }
+                Item::IfElse(v) => v.blocks(),
+                Item::Switch(v) => v.blocks(),
+                Item::TryCatch(v) => v.blocks(),
+                Item::ForLoop(v) => v.blocks(),
+                Item::RangeForLoop(v) => v.blocks(),
+                Item::WhileLoop(v) => v.blocks(),
+                Item::DoWhileLoop(v) => v.blocks(),
+                _ => continue,
+            };
+            for b in nested {
+                ret.extend(b.check_declarations());
+            }
+        }
+        ret
+    }
+
+    /// collects the required system includes into the supplied vector
+    fn collect_includes(&self, dialect: Dialect, incs: &mut Vec<&'static str>) {
+        for item in self.items.iter() {
+            let (exprs, nested) = match item {
+                Item::Assert(e) => {
+                    add_include(incs, dialect, "assert.h");
+                    (vec![e], Vec::new())
+                }
+                Item::FnCall(name, args) => {
+                    if let Some(hdr) = fn_call_include(name) {
+                        add_include(incs, dialect, hdr);
+                    }
+                    (args.iter().collect(), Vec::new())
+                }
+                Item::MethodCall(obj, _, args) => {
+                    let mut exprs = vec![obj];
+                    exprs.extend(args.iter());
+                    (exprs, Vec::new())
+                }
+                Item::Variable(v) => (v.value().into_iter().collect(), Vec::new()),
+                Item::Return(Some(e))
+                | Item::Throw(Some(e))
+                | Item::Increment(e)
+                | Item::Decrement(e)
+                | Item::Expr(e) => (vec![e], Vec::new()),
+                Item::Assign(lhs, rhs) | Item::CompoundAssign(lhs, _, rhs) => {
+                    (vec![lhs, rhs], Vec::new())
+                }
+                Item::IfElse(v) => (v.exprs(), v.blocks()),
+                Item::Switch(v) => (v.exprs(), v.blocks()),
+                Item::TryCatch(v) => (Vec::new(), v.blocks()),
+                Item::ForLoop(v) => (v.exprs(), v.blocks()),
+                Item::RangeForLoop(v) => (v.exprs(), v.blocks()),
+                Item::WhileLoop(v) => (v.exprs(), v.blocks()),
+                Item::DoWhileLoop(v) => (v.exprs(), v.blocks()),
+                _ => continue,
+            };
+            for e in exprs {
+                e.required_includes(dialect, incs);
+            }
+            for b in nested {
+                b.collect_includes(dialect, incs);
+            }
+        }
+    }
+
     /// adds a `continue` statement to the block
     pub fn continue_stmt(&mut self) -> &mut Self {
         self.items.push(Item::Continue);
@@ -178,6 +426,21 @@ impl Block {
         self
     }
 
+    /// adds a new try-catch block to the block
+    pub fn new_try(&mut self) -> &mut TryCatch {
+        self.try_catch(TryCatch::new());
+        match *self.items.last_mut().unwrap() {
+            Item::TryCatch(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// adds a try-catch block to the block
+    pub fn try_catch(&mut self, s: TryCatch) -> &mut Self {
+        self.items.push(Item::TryCatch(s));
+        self
+    }
+
     /// adds a new for loop to the block
     pub fn new_for_loop(&mut self, init: &Expr, guard: &Expr, step: &Expr) -> &mut ForLoop {
         let forloop = ForLoop::from_expr(init, guard, step);
@@ -188,12 +451,50 @@ impl Block {
         }
     }
 
+    /// adds a new for loop followed by the given exit label to the block
+    ///
+    /// Use [`Block::break_to_label`] in a nested loop to leave this loop.
+    ///
+    /// # Example
+    ///
+    /// `for (...) { ... }` => `for (...) { ... }`, `label:`, `;`
+    pub fn new_for_loop_with_exit(
+        &mut self,
+        label: &str,
+        init: &Expr,
+        guard: &Expr,
+        step: &Expr,
+    ) -> &mut ForLoop {
+        let idx = self.items.len();
+        self.for_loop(ForLoop::from_expr(init, guard, step));
+        self.exit_label(label);
+        match self.items[idx] {
+            Item::ForLoop(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
     /// adds a for loop to the block
     pub fn for_loop(&mut self, s: ForLoop) -> &mut Self {
         self.items.push(Item::ForLoop(s));
         self
     }
 
+    /// adds a new range-based for loop to the block
+    pub fn new_range_for(&mut self, decl: &str, range: &Expr) -> &mut RangeForLoop {
+        self.range_for_loop(RangeForLoop::new(decl, range));
+        match *self.items.last_mut().unwrap() {
+            Item::RangeForLoop(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// adds a range-based for loop to the block
+    pub fn range_for_loop(&mut self, s: RangeForLoop) -> &mut Self {
+        self.items.push(Item::RangeForLoop(s));
+        self
+    }
+
     /// adds a new while loop to the block
     pub fn new_while_loop(&mut self, cond: &Expr) -> &mut WhileLoop {
         self.while_loop(WhileLoop::new(cond));
@@ -203,6 +504,19 @@ impl Block {
         }
     }
 
+    /// adds a new while loop followed by the given exit label to the block
+    ///
+    /// Use [`Block::break_to_label`] in a nested loop to leave this loop.
+    pub fn new_while_loop_with_exit(&mut self, label: &str, cond: &Expr) -> &mut WhileLoop {
+        let idx = self.items.len();
+        self.while_loop(WhileLoop::new(cond));
+        self.exit_label(label);
+        match self.items[idx] {
+            Item::WhileLoop(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
     /// adds a while loop to the block
     pub fn while_loop(&mut self, s: WhileLoop) -> &mut Self {
         self.items.push(Item::WhileLoop(s));
@@ -233,6 +547,24 @@ impl Block {
         }
     }
 
+    /// declares a new local variable and returns an expression referring to it
+    ///
+    /// # Example
+    ///
+    /// `local("i", int)` => `int i;`, returning `i`
+    pub fn local(&mut self, name: &str, ty: Type) -> Expr {
+        self.new_variable(name, ty).to_expr()
+    }
+
+    /// declares a new initialized local variable and returns an expression referring to it
+    ///
+    /// # Example
+    ///
+    /// `local_init("i", int, 0)` => `int i = 0;`, returning `i`
+    pub fn local_init(&mut self, name: &str, ty: Type, val: Expr) -> Expr {
+        self.new_variable(name, ty).set_value(val).to_expr()
+    }
+
     /// adding a variable to the block
     pub fn variable(&mut self, var: Variable) -> &mut Self {
         self.items.push(Item::Variable(var));
@@ -262,12 +594,49 @@ impl Block {
         self
     }
 
+    /// return statement casting the expression to the given type
+    ///
+    /// # Example
+    ///
+    /// `len`, `int` => `return (int)(len);`
+    pub fn return_cast(&mut self, expr: Expr, ty: Type) -> &mut Self {
+        self.return_expr(expr.cast_to(ty))
+    }
+
+    /// return statement of a function returning `ret_ty`, casting the expression if
+    /// its type is known and differs from the return type, ignoring the top-level
+    /// qualifiers of both
+    ///
+    /// # Example
+    ///
+    /// `size_t len`, `int` => `return (int)(len);`, `int x`, `const int` => `return x;`
+    pub fn return_checked(&mut self, expr: Expr, ret_ty: &Type) -> &mut Self {
+        match expr.known_type() {
+            Some(ty) if !ty.to_unqualified().is_same(&ret_ty.to_unqualified()) => {
+                self.return_cast(expr, ret_ty.to_unqualified())
+            }
+            _ => self.return_expr(expr),
+        }
+    }
+
     /// return statement returning nothing
     pub fn return_none(&mut self) -> &mut Self {
         self.items.push(Item::Return(None));
         self
     }
 
+    /// throw statement throwing the given exception
+    pub fn throw(&mut self, expr: Expr) -> &mut Self {
+        self.items.push(Item::Throw(Some(expr)));
+        self
+    }
+
+    /// throw statement rethrowing the current exception
+    pub fn rethrow(&mut self) -> &mut Self {
+        self.items.push(Item::Throw(None));
+        self
+    }
+
     /// a printf statement
     pub fn printf(&mut self, format: &str, vars: Vec<Expr>) -> &mut Self {
         let mut vars = vars;
@@ -290,6 +659,51 @@ impl Block {
         self
     }
 
+    /// copies `size` bytes from `src` to `dst`: `memcpy(dst, src, size);`
+    ///
+    /// The call requires `string.h`, see [`Block::required_includes`].
+    pub fn memcpy(&mut self, dst: Expr, src: Expr, size: Expr) -> &mut Self {
+        self.fn_call("memcpy", vec![dst, src, size])
+    }
+
+    /// copies the bits of `src` into `dst`: `memcpy(&dst, &src, sizeof(dst));`
+    ///
+    /// This is the portable alternative to `Expr::bit_cast` in C. Both operands must be
+    /// lvalues of the same size, i.e., store an rvalue in a variable first. The call
+    /// requires `string.h`, see [`Block::required_includes`].
+    pub fn bit_cast(&mut self, dst: Expr, src: Expr) -> &mut Self {
+        let size = dst.size_of();
+        self.memcpy(dst.addr_of(), src.addr_of(), size)
+    }
+
+    /// fills `size` bytes at `dst` with `val`: `memset(dst, val, size);`
+    ///
+    /// The call requires `string.h`, see [`Block::required_includes`].
+    pub fn memset(&mut self, dst: Expr, val: Expr, size: Expr) -> &mut Self {
+        self.fn_call("memset", vec![dst, val, size])
+    }
+
+    /// allocates an array of `count` elements of the type and assigns it to `var`
+    ///
+    /// The result of `malloc` is cast to the pointer type, which C++ requires. The call
+    /// requires `stdlib.h`, see [`Block::required_includes`].
+    ///
+    /// # Example
+    ///
+    /// `p`, `n`, `int` => `p = (int *)(malloc((n * sizeof(int))));`
+    pub fn alloc(&mut self, var: Expr, count: Expr, elem_ty: Type) -> &mut Self {
+        let size = Expr::binop(count, "*", Expr::size_of_type(elem_ty.clone()));
+        let mem = Expr::fn_call("malloc", vec![size]).cast_to(elem_ty.to_ptr());
+        self.assign(var, mem)
+    }
+
+    /// releases the memory pointed to by `var`: `free(var);`
+    ///
+    /// The call requires `stdlib.h`, see [`Block::required_includes`].
+    pub fn free(&mut self, var: Expr) -> &mut Self {
+        self.fn_call("free", vec![var])
+    }
+
     /// a method call
     pub fn method_call(&mut self, obj: Expr, method: &str, args: Vec<Expr>) -> &mut Self {
         self.items.push(Item::MethodCall(obj, String::from(method), args));
@@ -302,9 +716,16 @@ impl Block {
             match &item {
                 Item::Comment(v) => v.fmt(fmt)?,
                 Item::NewLine => writeln!(fmt)?,
+                Item::Assert(v) => {
+                    write!(fmt, "assert(")?;
+                    v.fmt(fmt)?;
+                    writeln!(fmt, ");")?;
+                }
                 Item::Break => writeln!(fmt, "break;")?,
+                Item::Null => writeln!(fmt, ";")?,
                 Item::Continue => writeln!(fmt, "continue;")?,
                 Item::Raw(v) => writeln!(fmt, "{v};")?,
+                Item::RawNoSemi(v) => writeln!(fmt, "{v}")?,
                 Item::Expr(v) => {
                     v.fmt(fmt)?;
                     writeln!(fmt, ";")?;
@@ -317,9 +738,25 @@ impl Block {
                     r.fmt(fmt)?;
                     writeln!(fmt, ";")?;
                 }
+                Item::CompoundAssign(l, op, r) => {
+                    l.fmt(fmt)?;
+                    write!(fmt, " {op}= ")?;
+                    r.fmt(fmt)?;
+                    writeln!(fmt, ";")?;
+                }
+                Item::Increment(v) => {
+                    v.fmt(fmt)?;
+                    writeln!(fmt, "++;")?;
+                }
+                Item::Decrement(v) => {
+                    v.fmt(fmt)?;
+                    writeln!(fmt, "--;")?;
+                }
                 Item::IfElse(v) => v.fmt(fmt)?,
                 Item::Switch(v) => v.fmt(fmt)?,
+                Item::TryCatch(v) => v.fmt(fmt)?,
                 Item::ForLoop(v) => v.fmt(fmt)?,
+                Item::RangeForLoop(v) => v.fmt(fmt)?,
                 Item::WhileLoop(v) => v.fmt(fmt)?,
                 Item::DoWhileLoop(v) => v.fmt(fmt)?,
                 Item::Variable(v) => v.fmt_def(fmt)?,
@@ -329,6 +766,12 @@ impl Block {
                     v.fmt(fmt)?;
                     writeln!(fmt, ";")?
                 }
+                Item::Throw(None) => writeln!(fmt, "throw;")?,
+                Item::Throw(Some(v)) => {
+                    write!(fmt, "throw ")?;
+                    v.fmt(fmt)?;
+                    writeln!(fmt, ";")?
+                }
                 Item::FnCall(name, args) => {
                     write!(fmt, "{name}(")?;
                     for (i, arg) in args.iter().enumerate() {
@@ -28,8 +28,8 @@
 //! The `Class` module provides functionality to express a C++ class with its
 //! attributes and method members.
 //!
-//! The class implementation does currently not support multiple inheritance,
-//! or the definition of nested types, or generics.
+//! The class implementation does currently not support the definition of nested
+//! types, or generics.
 
 use std::fmt::{self, Display, Write};
 
@@ -37,6 +37,19 @@ use crate::{
     Attribute, BaseType, Constructor, Destructor, Doc, Formatter, Method, Type, Visibility,
 };
 
+/// Defines a base class of a C++ class
+#[derive(Debug, Clone)]
+struct BaseClass {
+    /// the name of the base class
+    name: String,
+
+    /// the visibility of the inheritance
+    visibility: Visibility,
+
+    /// whether this is a virtual base class
+    is_virtual: bool,
+}
+
 /// Defines a C++ class
 #[derive(Debug, Clone)]
 pub struct Class {
@@ -46,8 +59,8 @@ pub struct Class {
     /// Documentation comment of the class
     doc: Option<Doc>,
 
-    /// Parent class with its visibility
-    base: Option<(Visibility, String)>,
+    /// Parent classes with their visibility
+    bases: Vec<BaseClass>,
 
     /// Class constructor methods
     constructors: Vec<Constructor>,
@@ -60,6 +73,15 @@ pub struct Class {
 
     /// Field members of the class with their visibility
     attributes: Vec<Attribute>,
+
+    /// whether discarding a returned value of this class type is warned about
+    is_nodiscard: bool,
+
+    /// the `__declspec` of the class, e.g., `dllexport`
+    declspec: Option<String>,
+
+    /// the macro selecting the DLL storage class, see `Scope::new_export_macro`
+    export_macro: Option<String>,
 }
 
 impl Class {
@@ -68,12 +90,40 @@ impl Class {
         Self {
             name: name.to_string(),
             doc: None,
-            base: None,
+            bases: Vec::new(),
             destructor: None,
             constructors: Vec::new(),
             methods: Vec::new(),
             attributes: Vec::new(),
+            is_nodiscard: false,
+            declspec: None,
+            export_macro: None,
+        }
+    }
+
+    /// obtains the name of the class
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// sets the name of the class
+    ///
+    /// The constructors and the destructor carry the class name and are renamed as well,
+    /// and so are the references to the class in the signatures of the constructors and
+    /// methods, e.g., of the copy constructor and the assignment operators.
+    pub fn set_name(&mut self, name: &str) -> &mut Self {
+        let old = std::mem::replace(&mut self.name, String::from(name));
+        for c in &mut self.constructors {
+            c.set_name(name);
+            c.rename_class(&old, name);
         }
+        for m in &mut self.methods {
+            m.rename_class(&old, name);
+        }
+        if let Some(d) = &mut self.destructor {
+            d.set_name(name);
+        }
+        self
     }
 
     /// Returns the corresponding type for this class
@@ -101,9 +151,37 @@ impl Class {
         self
     }
 
-    /// sets the base class
+    /// sets the base class, replacing any previously added base classes
     pub fn set_base(&mut self, base: &str, visibility: Visibility) -> &mut Self {
-        self.base = Some((visibility, base.to_string()));
+        self.bases.clear();
+        self.push_base(base, visibility)
+    }
+
+    /// adds a base class
+    ///
+    /// # Example
+    ///
+    /// class Foo : public A  => class Foo : public A, public B
+    pub fn push_base(&mut self, base: &str, visibility: Visibility) -> &mut Self {
+        self.bases.push(BaseClass {
+            name: base.to_string(),
+            visibility,
+            is_virtual: false,
+        });
+        self
+    }
+
+    /// adds a virtual base class
+    ///
+    /// # Example
+    ///
+    /// class Foo  => class Foo : public virtual A
+    pub fn push_virtual_base(&mut self, base: &str, visibility: Visibility) -> &mut Self {
+        self.bases.push(BaseClass {
+            name: base.to_string(),
+            visibility,
+            is_virtual: true,
+        });
         self
     }
 
@@ -129,6 +207,12 @@ impl Class {
         self.attributes.iter_mut().find(|f| f.name() == name)
     }
 
+    /// removes the attribute with the given name from the class, returning it
+    pub fn remove_attribute(&mut self, name: &str) -> Option<Attribute> {
+        let idx = self.attributes.iter().position(|f| f.name() == name)?;
+        Some(self.attributes.remove(idx))
+    }
+
     /// obtains a reference to the attribute with the given index (starting at 0)
     pub fn attribute_by_idx(&self, idx: usize) -> Option<&Attribute> {
         self.attributes.get(idx)
@@ -163,6 +247,61 @@ impl Class {
         self.methods.iter_mut().find(|f| f.name() == name)
     }
 
+    /// removes all methods with the given name, including overloads, returning them
+    pub fn remove_method(&mut self, name: &str) -> Vec<Method> {
+        let (removed, kept) =
+            std::mem::take(&mut self.methods).into_iter().partition(|m| m.name() == name);
+        self.methods = kept;
+        removed
+    }
+
+    /// removes the overload of the method with the given parameter types, returning it
+    ///
+    /// # Example
+    ///
+    /// `set`, `[int32_t]` removes `void set(int32_t x);` but keeps `void set(void);`
+    pub fn remove_method_by_signature(&mut self, name: &str, params: &[Type]) -> Option<Method> {
+        let idx = self.methods.iter().position(|m| m.has_signature(name, params))?;
+        Some(self.methods.remove(idx))
+    }
+
+    /// replaces the method with the given name, returning the previous method
+    ///
+    /// The new method takes the place of the first method with the given name, and
+    /// all other overloads of that name are removed. If there is no such method, the
+    /// new method is added to the class.
+    pub fn replace_method(&mut self, name: &str, method: Method) -> Option<Method> {
+        let Some(idx) = self.methods.iter().position(|m| m.name() == name) else {
+            self.methods.push(method);
+            return None;
+        };
+        let old = std::mem::replace(&mut self.methods[idx], method);
+        let mut i = 0;
+        self.methods.retain(|m| {
+            i += 1;
+            i - 1 == idx || m.name() != name
+        });
+        Some(old)
+    }
+
+    /// adds a pair of const and non-const accessor methods to the class
+    ///
+    /// # Example
+    ///
+    /// `at`, `T`  => `T & at(size_t idx);` and `const T & at(size_t idx) const;`
+    pub fn new_accessor_pair(&mut self, name: &str, elem_ty: Type) -> &mut Self {
+        let mut m = Method::new(name, elem_ty.to_ref());
+        m.set_public().new_param("idx", Type::new_size());
+        self.methods.push(m);
+
+        let mut ty = elem_ty;
+        ty.set_value_const().reference();
+        let mut m = Method::new(name, ty);
+        m.set_public().set_const().new_param("idx", Type::new_size());
+        self.methods.push(m);
+        self
+    }
+
     pub fn new_constructor(&mut self) -> &mut Constructor {
         self.constructors.push(Constructor::new(self.name.as_str()));
         self.constructors.last_mut().unwrap()
@@ -174,6 +313,99 @@ impl Class {
         self.destructor.as_mut().unwrap()
     }
 
+    /// sets the class to be `[[nodiscard]]`
+    ///
+    /// # Example
+    ///
+    /// `class Error` => `class [[nodiscard]] Error`
+    pub fn toggle_nodiscard(&mut self, val: bool) -> &mut Self {
+        self.is_nodiscard = val;
+        self
+    }
+
+    /// makes the class `[[nodiscard]]`
+    pub fn set_nodiscard(&mut self) -> &mut Self {
+        self.toggle_nodiscard(true)
+    }
+
+    /// sets the `__declspec` of the class, e.g., `dllexport`
+    ///
+    /// The specifier is only emitted for MSVC, see `Dialect::Msvc`. Otherwise, `dllexport`
+    /// becomes `__attribute__((visibility("default")))`.
+    ///
+    /// # Example
+    ///
+    /// `class Foo` => `class __declspec(dllexport) Foo`
+    pub fn declspec(&mut self, spec: &str) -> &mut Self {
+        self.declspec = Some(String::from(spec));
+        self
+    }
+
+    /// sets the macro selecting the DLL storage class of the class
+    ///
+    /// # Example
+    ///
+    /// `class Foo` => `class MYLIB_API Foo`
+    pub fn export_macro(&mut self, name: &str) -> &mut Self {
+        self.export_macro = Some(String::from(name));
+        self
+    }
+
+    /// declares the rule-of-five special members of the class
+    ///
+    /// The copy and move constructors and assignment operators are either defaulted
+    /// or deleted. The destructor is defaulted, or declared to be user-defined.
+    ///
+    /// # Example
+    ///
+    /// Foo(Foo const & other) = delete;
+    /// Foo & operator=(Foo const & other) = delete;
+    pub fn declare_rule_of_five(&mut self, defaulted: bool) -> &mut Self {
+        let name = self.name.clone();
+        let class_ty = Type::new_class(&name);
+
+        let d = self.new_destructor();
+        d.set_default(defaulted);
+
+        for is_move in [false, true] {
+            let c = self.new_constructor();
+            c.set_copy(!is_move).set_move(is_move);
+            if defaulted {
+                c.default();
+            } else {
+                c.delete();
+            }
+
+            let param_ty = if is_move {
+                class_ty.to_rvalue_ref()
+            } else {
+                let mut t = class_ty.clone();
+                t.constant().reference();
+                t
+            };
+            let m = self.new_method("operator=", class_ty.to_ref());
+            m.set_public().new_param("other", param_ty);
+            if defaulted {
+                m.set_default();
+            } else {
+                m.set_delete();
+            }
+        }
+        self
+    }
+
+    /// obtains the types used by the attributes and methods of the class
+    pub(crate) fn used_types(&self) -> Vec<Type> {
+        let mut ret: Vec<Type> = self.attributes.iter().map(|a| a.to_type()).collect();
+        for c in &self.constructors {
+            ret.extend(c.used_types());
+        }
+        for m in &self.methods {
+            ret.extend(m.used_types());
+        }
+        ret
+    }
+
     pub fn do_fmt_class_scope(&self, fmt: &mut Formatter<'_>, decl_only: bool) -> fmt::Result {
         if let Some(ref docs) = self.doc {
             docs.fmt(fmt)?;
@@ -195,11 +427,30 @@ impl Class {
             return Ok(());
         }
 
-        write!(fmt, "class {}", self.name)?;
+        write!(fmt, "class ")?;
+        if let Some(name) = &self.export_macro {
+            write!(fmt, "{name} ")?;
+        }
+        if let Some(spec) = &self.declspec {
+            fmt.write_declspec(spec)?;
+        }
+        if self.is_nodiscard {
+            write!(fmt, "[[nodiscard]] ")?;
+        }
+        write!(fmt, "{}", self.name)?;
 
         // the derived class
-        if let Some(p) = &self.base {
-            write!(fmt, " : {} {}", p.0, p.1)?;
+        for (i, b) in self.bases.iter().enumerate() {
+            if i == 0 {
+                write!(fmt, " : ")?;
+            } else {
+                write!(fmt, ", ")?;
+            }
+            if b.is_virtual {
+                write!(fmt, "{} virtual {}", b.visibility, b.name)?;
+            } else {
+                write!(fmt, "{} {}", b.visibility, b.name)?;
+            }
         }
 
         let pub_attr = self.attributes.iter().filter(|a| a.is_public()).count();
@@ -324,12 +575,9 @@ impl Class {
     }
 }
 
+/// Displays the declaration of the class, use `fmt_def` for the definitions
 impl Display for Class {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut ret = String::new();
-        self.fmt_def(&mut Formatter::new(&mut ret)).unwrap();
-        write!(f, "{ret}")?;
-
         let mut ret = String::new();
         self.fmt_decl(&mut Formatter::new(&mut ret)).unwrap();
         write!(f, "{ret}")
@@ -30,7 +30,7 @@
 //! comments. Not this will create simple comments, as opposed to document comments
 //! for which there is the `Doc` module.
 
-use std::fmt::{self, Write};
+use std::fmt::{self, Display, Write};
 
 use crate::formatter::Formatter;
 
@@ -42,6 +42,9 @@ pub struct Comment {
 
     /// defines whether the comment is a heading
     is_heading: bool,
+
+    /// defines whether the comment is emitted as a `/* ... */` block
+    is_block: bool,
 }
 
 impl Comment {
@@ -55,6 +58,7 @@ impl Comment {
         Self {
             comment,
             is_heading: false,
+            is_block: false,
         }
     }
 
@@ -67,7 +71,8 @@ impl Comment {
     pub fn new_heading(comment: &str) -> Self {
         Self {
             comment: comment.to_string(),
-            is_heading: false,
+            is_heading: true,
+            is_block: false,
         }
     }
 
@@ -77,10 +82,43 @@ impl Comment {
         self
     }
 
+    /// sets the comment to be emitted as a `/* ... */` block comment
+    pub fn toggle_block(&mut self, val: bool) -> &mut Self {
+        self.is_block = val;
+        self
+    }
+
+    /// converts the comment into a block comment
+    pub fn set_block(&mut self) -> &mut Self {
+        self.toggle_block(true)
+    }
+
+    /// formats the comment as a block comment, escaping any `*/` in the body
+    ///
+    /// # Example
+    ///
+    /// `a */ b` => `/* a * / b */`
+    fn fmt_block(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        let mut lines = self.comment.lines().map(|l| l.replace("*/", "* /"));
+        match (lines.next(), lines.next()) {
+            (None, _) => writeln!(fmt, "/* */"),
+            (Some(line), None) => writeln!(fmt, "/* {line} */"),
+            (Some(first), Some(second)) => {
+                writeln!(fmt, "/*")?;
+                writeln!(fmt, " * {first}")?;
+                writeln!(fmt, " * {second}")?;
+                for line in lines {
+                    writeln!(fmt, " * {line}")?;
+                }
+                writeln!(fmt, " */")
+            }
+        }
+    }
+
     /// pushes the heading separator
     fn push_heading(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         if self.is_heading {
-            for _ in 0..(100 - fmt.get_indent()) {
+            for _ in 0..fmt.line_width().saturating_sub(fmt.get_indent()) {
                 write!(fmt, "/")?;
             }
             writeln!(fmt)?;
@@ -91,6 +129,9 @@ impl Comment {
     // formats the comment block
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         // writeln!(fmt)?;
+        if self.is_block {
+            return self.fmt_block(fmt);
+        }
         self.push_heading(fmt)?;
         for line in self.comment.lines() {
             writeln!(fmt, "// {line}")?;
@@ -104,3 +145,11 @@ impl Default for Comment {
         Self::new()
     }
 }
+
+impl Display for Comment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ret = String::new();
+        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        write!(f, "{ret}")
+    }
+}
@@ -29,7 +29,8 @@
 
 use std::fmt::{self, Write};
 
-use crate::{BaseType, Block, Doc, Expr, Formatter, MethodParam, Type, Visibility};
+use crate::expr::escape_str;
+use crate::{BaseType, Block, Doc, Expr, Formatter, MethodParam, TemplateArg, Type, Visibility};
 
 /// holds a method definition
 #[derive(Debug, Clone)]
@@ -55,6 +56,9 @@ pub struct Constructor {
     /// marks the constructor as deleted
     is_delete: bool,
 
+    /// the reason for deleting, `= delete("reason")`
+    delete_reason: Option<String>,
+
     /// this is a copy constructor
     is_copy: bool,
 
@@ -64,6 +68,18 @@ pub struct Constructor {
     /// wheter the definition is inside of the class
     is_inside: bool,
 
+    /// marks the constructor as explicit
+    is_explicit: bool,
+
+    /// marks the constructor as constexpr
+    is_constexpr: bool,
+
+    /// marks the constructor as noexcept
+    is_noexcept: bool,
+
+    /// the class template arguments of an explicit specialization of the constructor
+    specialization: Option<Vec<TemplateArg>>,
+
     /// the body of the method, a sequence of statements
     body: Block,
 }
@@ -79,13 +95,29 @@ impl Constructor {
             initializers: Vec::new(),
             is_default: false,
             is_delete: false,
+            delete_reason: None,
             is_copy: false,
             is_move: false,
             is_inside: false,
+            is_explicit: false,
+            is_constexpr: false,
+            is_noexcept: false,
+            specialization: None,
             body: Block::new(),
         }
     }
 
+    /// obtains the name of the constructor
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// renames the constructor, used when the enclosing class is renamed
+    pub(crate) fn set_name(&mut self, name: &str) -> &mut Self {
+        self.name = String::from(name);
+        self
+    }
+
     /// creates a new move constructor
     pub fn new_move(name: &str) -> Self {
         let mut c = Constructor::new(name);
@@ -201,6 +233,7 @@ impl Constructor {
     pub fn push_initializer(&mut self, field_name: &str, value: Expr) -> &mut Self {
         self.initializers.push(Expr::FnCall {
             name: String::from(field_name),
+            targs: Vec::new(),
             args: vec![value],
         });
         self
@@ -219,7 +252,7 @@ impl Constructor {
     pub fn set_default(&mut self, val: bool) -> &mut Self {
         if val {
             self.body.clear();
-            if !self.is_copy {
+            if !(self.is_copy || self.is_move) {
                 self.params.clear();
             }
             self.is_delete = false;
@@ -241,7 +274,7 @@ impl Constructor {
     pub fn set_delete(&mut self, val: bool) -> &mut Self {
         if val {
             self.body.clear();
-            if !self.is_copy {
+            if !(self.is_copy || self.is_move) {
                 self.params.clear();
             }
             self.is_default = false;
@@ -255,6 +288,18 @@ impl Constructor {
         self.set_delete(true)
     }
 
+    /// deletes the constructor giving the reason for the deletion
+    ///
+    /// The reason requires C++26 and is only emitted if enabled with `Scope::set_delete_reasons`.
+    ///
+    /// # Example
+    ///
+    /// Foo()   -> Foo() = delete("use create()");
+    pub fn set_delete_reason(&mut self, reason: &str) -> &mut Self {
+        self.delete_reason = Some(String::from(reason));
+        self.delete()
+    }
+
     /// marks this constructor as the copy constructor
     ///
     /// # Example
@@ -283,7 +328,7 @@ impl Constructor {
     pub fn set_move(&mut self, val: bool) -> &mut Self {
         if val {
             let mut ty = Type::new(BaseType::Class(self.name.clone()));
-            ty.reference().reference();
+            ty.rvalue_reference();
             self.params = vec![MethodParam::new("other", ty)];
         }
         self.is_move = val;
@@ -295,6 +340,61 @@ impl Constructor {
         self.set_move(true)
     }
 
+    /// marks the constructor as explicit, disallowing implicit conversions
+    ///
+    /// # Example
+    ///
+    /// Foo(int x)   -> explicit Foo(int x)
+    pub fn set_explicit(&mut self, val: bool) -> &mut Self {
+        self.is_explicit = val;
+        self
+    }
+
+    /// makes the constructor explicit
+    pub fn explicit(&mut self) -> &mut Self {
+        self.set_explicit(true)
+    }
+
+    /// marks the constructor as constexpr
+    ///
+    /// # Example
+    ///
+    /// Foo(int x)   -> constexpr Foo(int x)
+    pub fn set_constexpr(&mut self, val: bool) -> &mut Self {
+        self.is_constexpr = val;
+        self
+    }
+
+    /// makes the constructor constexpr
+    pub fn constexpr(&mut self) -> &mut Self {
+        self.set_constexpr(true)
+    }
+
+    /// marks the constructor as noexcept
+    ///
+    /// # Example
+    ///
+    /// Foo(int x)   -> Foo(int x) noexcept
+    pub fn set_noexcept(&mut self, val: bool) -> &mut Self {
+        self.is_noexcept = val;
+        self
+    }
+
+    /// makes the constructor noexcept
+    pub fn noexcept(&mut self) -> &mut Self {
+        self.set_noexcept(true)
+    }
+
+    /// explicitly specializes the constructor for the given class template arguments
+    ///
+    /// # Example
+    ///
+    /// `[int]` => `template <> Foo<int>::Foo(void) { ... }`
+    pub fn set_specialization(&mut self, args: Vec<TemplateArg>) -> &mut Self {
+        self.specialization = Some(args);
+        self
+    }
+
     /// sets the definition localtion of the method
     pub fn set_inside_def(&mut self, val: bool) -> &mut Self {
         self.is_inside = val;
@@ -321,6 +421,20 @@ impl Constructor {
         &mut self.body
     }
 
+    /// obtains the types used by the constructor
+    pub(crate) fn used_types(&self) -> Vec<Type> {
+        let mut ret: Vec<Type> = self.params.iter().map(|p| p.to_type()).collect();
+        ret.extend(self.body.used_types());
+        ret
+    }
+
+    /// renames the class in the parameter types, e.g., of the copy constructor
+    pub(crate) fn rename_class(&mut self, from: &str, to: &str) {
+        for p in &mut self.params {
+            p.rename_class(from, to);
+        }
+    }
+
     /// Formats the attribute using the given formatter.
     pub fn do_fmt(&self, fmt: &mut Formatter<'_>, decl_only: bool) -> fmt::Result {
         if !self.body.is_empty() | self.doc.is_some() {
@@ -331,10 +445,31 @@ impl Constructor {
             docs.fmt(fmt)?;
         }
 
+        let spec = self.specialization.as_deref().filter(|_| !decl_only);
+        if spec.is_some() {
+            writeln!(fmt, "template <>")?;
+        }
+
+        // `explicit` is only allowed on the declaration inside the class
+        if self.is_explicit && (decl_only || self.is_inside) {
+            write!(fmt, "explicit ")?;
+        }
+
+        // the scoped name of the definition is separated by a space
+        if self.is_constexpr {
+            write!(fmt, "constexpr")?;
+            if decl_only {
+                write!(fmt, " ")?;
+            }
+        }
+
         if decl_only {
             write!(fmt, "{}", self.name)?;
         } else {
-            fmt.write_scoped_name(self.name.as_str())?;
+            if !fmt.is_start_of_line() {
+                write!(fmt, " ")?;
+            }
+            fmt.write_scoped_specialized(self.name.as_str(), spec.unwrap_or_default())?;
         }
 
         if self.params.is_empty() {
@@ -350,12 +485,21 @@ impl Constructor {
             write!(fmt, ")")?;
         }
 
+        if self.is_noexcept {
+            write!(fmt, " noexcept")?;
+        }
+
         if self.body.is_empty() && self.is_default {
             return writeln!(fmt, " = default;");
         }
 
         if self.body.is_empty() && self.is_delete {
-            return writeln!(fmt, " = delete;");
+            return match &self.delete_reason {
+                Some(r) if fmt.delete_reasons() => {
+                    writeln!(fmt, " = delete(\"{}\");", escape_str(r))
+                }
+                _ => writeln!(fmt, " = delete;"),
+            };
         }
 
         // if we want to have the declaration only, then do that,
@@ -416,6 +560,9 @@ pub struct Destructor {
     /// marks the constructor as deleted
     is_delete: bool,
 
+    /// the reason for deleting, `= delete("reason")`
+    delete_reason: Option<String>,
+
     /// wheter the definition is inside of the class
     is_inside: bool,
 
@@ -434,12 +581,24 @@ impl Destructor {
             doc: None,
             is_default: false,
             is_delete: false,
+            delete_reason: None,
             is_inside: false,
             is_pure: false,
             body: Block::new(),
         }
     }
 
+    /// obtains the name of the destructor
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// renames the destructor, used when the enclosing class is renamed
+    pub(crate) fn set_name(&mut self, name: &str) -> &mut Self {
+        self.name = String::from(name);
+        self
+    }
+
     /// creates a new move constructor
     pub fn new_delete(name: &str) -> Self {
         let mut c = Destructor::new(name);
@@ -513,6 +672,18 @@ impl Destructor {
         self.set_delete(true)
     }
 
+    /// deletes the destructor giving the reason for the deletion
+    ///
+    /// The reason requires C++26 and is only emitted if enabled with `Scope::set_delete_reasons`.
+    ///
+    /// # Example
+    ///
+    /// ~Foo()   -> ~Foo() = delete("never destroyed");
+    pub fn set_delete_reason(&mut self, reason: &str) -> &mut Self {
+        self.delete_reason = Some(String::from(reason));
+        self.delete()
+    }
+
     /// sets the definition localtion of the method
     pub fn set_inside_def(&mut self, val: bool) -> &mut Self {
         self.is_inside = val;
@@ -579,7 +750,12 @@ impl Destructor {
         }
 
         if self.body.is_empty() && self.is_delete {
-            return writeln!(fmt, " = delete;");
+            return match &self.delete_reason {
+                Some(r) if fmt.delete_reasons() => {
+                    writeln!(fmt, " = delete(\"{}\");", escape_str(r))
+                }
+                _ => writeln!(fmt, " = delete;"),
+            };
         }
 
         if self.is_pure {
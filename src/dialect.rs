@@ -0,0 +1,67 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2022 Reto Achermann
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Dialect
+//!
+//! This module defines the language dialect of the generated code. Some constructs
+//! are spelled differently in C and C++, e.g., `_Noreturn` vs. `[[noreturn]]`, or
+//! `restrict` vs. `__restrict`.
+
+/// the language dialect of the generated code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    /// ISO C (C11)
+    #[default]
+    C,
+    /// ISO C++
+    Cpp,
+    /// C++ as accepted by the Microsoft Visual C++ compiler
+    Msvc,
+}
+
+impl Dialect {
+    /// tests whether the dialect is a C++ dialect
+    pub fn is_cpp(&self) -> bool {
+        matches!(self, Dialect::Cpp | Dialect::Msvc)
+    }
+
+    /// obtains the name of the C standard library header in this dialect
+    ///
+    /// # Example
+    ///
+    /// `string.h` => `cstring` (C++)
+    pub fn std_header(&self, hdr: &'static str) -> &'static str {
+        if !self.is_cpp() {
+            return hdr;
+        }
+        match hdr {
+            "assert.h" => "cassert",
+            "stdint.h" => "cstdint",
+            "stdlib.h" => "cstdlib",
+            "string.h" => "cstring",
+            _ => hdr,
+        }
+    }
+}
@@ -72,11 +72,46 @@ impl Doc {
         self
     }
 
+    /// adds a single line verbatim to the documentation block, bypassing any wrapping.
+    pub fn add_raw_line(&mut self, line: &str) -> &mut Self {
+        self.docs.push(line.to_string());
+        self
+    }
+
+    /// adds a fenced code block to the documentation, the code is kept verbatim.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// /// ```
+    /// /// foo(bar);
+    /// /// ```
+    /// ```
+    pub fn add_code_block(&mut self, code: &str) -> &mut Self {
+        self.add_raw_line("```");
+        for l in code.lines() {
+            self.add_raw_line(l);
+        }
+        self.add_raw_line("```")
+    }
+
     /// adds a new textblock as documentation comments, while breaking long lines.
+    ///
+    /// Lines within code fences (```` ``` ````) are added verbatim.
     pub fn add_text(&mut self, text: &str) -> &mut Self {
         let mut res = self;
         let lines = text.lines();
+        let mut in_code = false;
         for l in lines {
+            if l.trim_start().starts_with("```") {
+                in_code = !in_code;
+                res = res.add_raw_line(l);
+                continue;
+            }
+            if in_code {
+                res = res.add_raw_line(l);
+                continue;
+            }
             if l.is_empty() || l == "\n" {
                 res = res.add_line("");
                 continue;
@@ -100,6 +135,22 @@ impl Doc {
         res
     }
 
+    /// formats the documentation block as a trailing comment on a single line
+    ///
+    /// # Example
+    ///
+    /// `RED,  ///< the color red`
+    pub fn fmt_trailing(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "///< {}", self.to_single_line())
+    }
+
+    /// joins the non-empty lines of the documentation block into a single line
+    pub(crate) fn to_single_line(&self) -> String {
+        let text: Vec<&str> =
+            self.docs.iter().map(|l| l.trim()).filter(|l| !l.is_empty()).collect();
+        text.join(" ")
+    }
+
     /// formats the documentation block as a string.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         for line in &self.docs {
@@ -31,7 +31,7 @@
 use std::fmt;
 use std::fmt::Write;
 
-use crate::{Doc, Formatter, Type, Variant};
+use crate::{Block, Doc, Expr, Formatter, Function, Switch, Type, Variant};
 
 /// Defines a C enum.
 #[derive(Debug, Clone)]
@@ -65,6 +65,17 @@ impl Enum {
         }
     }
 
+    /// obtains the name of the enum
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// sets the name of the enum
+    pub fn set_name(&mut self, name: &str) -> &mut Self {
+        self.name = String::from(name);
+        self
+    }
+
     /// converts the enum into a type
     pub fn to_type(&self) -> Type {
         Type::new_enum(&self.name)
@@ -98,6 +109,68 @@ impl Enum {
         self
     }
 
+    /// assigns successive values `0, 1, 2, ...` to the variants without a value
+    ///
+    /// Explicitly set values are kept and the numbering continues from them.
+    /// Returns `None` and leaves the variants unchanged if the values run out.
+    pub fn auto_sequential(&mut self) -> Option<&mut Self> {
+        self.auto_values(0, |val| val.checked_add(1))
+    }
+
+    /// assigns successive powers of two `1, 2, 4, ...` to the variants without a value
+    ///
+    /// Explicitly set values are kept and the next variant gets the next power of two.
+    /// Returns `None` and leaves the variants unchanged if the values run out.
+    pub fn auto_flags(&mut self) -> Option<&mut Self> {
+        self.auto_values(1, |val| val.checked_add(1)?.checked_next_power_of_two())
+    }
+
+    /// assigns values to the variants without one, starting at `first` and deriving
+    /// the next value from the previous one with `succ`
+    fn auto_values(&mut self, first: u64, succ: impl Fn(u64) -> Option<u64>) -> Option<&mut Self> {
+        let mut next = Some(first);
+        let mut values = Vec::with_capacity(self.variants.len());
+        for v in &self.variants {
+            let val = match v.value() {
+                Some(val) => val,
+                None => next?,
+            };
+            next = succ(val);
+            values.push(val);
+        }
+        for (v, val) in self.variants.iter_mut().zip(values) {
+            v.set_value(val);
+        }
+        Some(self)
+    }
+
+    /// prefixes the names of all variants with the given string
+    ///
+    /// # Example
+    ///
+    /// `FOO_`: `A`, `B` => `FOO_A`, `FOO_B`
+    pub fn prefix_variants(&mut self, prefix: &str) -> &mut Self {
+        self.map_variant_names(|n| format!("{prefix}{n}"))
+    }
+
+    /// rewrites the names of all variants with the given function
+    ///
+    /// # Example
+    ///
+    /// `str::to_uppercase`: `red`, `green` => `RED`, `GREEN`
+    pub fn map_variant_names(&mut self, f: impl Fn(&str) -> String) -> &mut Self {
+        for v in self.variants.iter_mut() {
+            let name = f(v.name());
+            v.set_name(&name);
+        }
+        self
+    }
+
+    /// obtains the variants of the enum
+    pub fn variants(&self) -> &[Variant] {
+        &self.variants
+    }
+
     /// obtains a reference to the field with the given name
     pub fn variant_by_name(&self, name: &str) -> Option<&Variant> {
         self.variants.iter().find(|f| f.name() == name)
@@ -118,6 +191,30 @@ impl Enum {
         self.variants.get_mut(idx)
     }
 
+    /// creates a function converting a value of the enum into its variant name
+    ///
+    /// # Example
+    ///
+    /// `enum color` => `const char * color_to_string(enum color val)`
+    pub fn to_string_function(&self) -> Function {
+        let mut ret = Type::new_char();
+        ret.const_value().pointer();
+
+        let mut f = Function::with_string(format!("{}_to_string", self.name), ret);
+        let param = f.new_param("val", self.to_type()).to_expr();
+
+        let mut switch = Switch::from_enum_with(&param, self, |v| {
+            let mut b = Block::new();
+            b.return_expr(Expr::new_str(v.name()));
+            b
+        });
+        let mut default = Block::new();
+        default.return_expr(Expr::new_str("unknown"));
+        switch.set_default(default);
+        f.body().switch(switch);
+        f
+    }
+
     /// Formats a forward declaration for the enum
     pub fn fmt_decl(&self, fmt: &mut Formatter) -> fmt::Result {
         write!(fmt, "enum {};   // forward declaration", self.name)
@@ -131,16 +228,12 @@ impl Enum {
 
         write!(fmt, "enum {}", self.name)?;
         fmt.block(|fmt| {
-            let mut first = true;
-            for variant in &self.variants {
-                if first {
-                    first = false;
-                } else {
-                    writeln!(fmt, ",")?;
-                }
-                variant.fmt(fmt)?;
+            // align the trailing comments, leaving space for the comma
+            let width = self.variants.iter().map(|v| v.decl_width()).max().unwrap_or(0);
+            let col = fmt.get_indent() + width + 2;
+            for (i, variant) in self.variants.iter().enumerate() {
+                variant.do_fmt(fmt, i + 1 == self.variants.len(), col)?;
             }
-
             Ok(())
         })?;
         writeln!(fmt, ";")
@@ -29,7 +29,9 @@
 
 use std::fmt::{self, Display, Write};
 
-use crate::{Attribute, BaseType, Formatter, FunctionParam, MethodParam, Type};
+use crate::{
+    Attribute, BaseType, Dialect, Formatter, FunctionParam, MethodParam, TemplateArg, Type,
+};
 
 /// Defines an statement
 #[derive(Debug, Clone)]
@@ -41,7 +43,13 @@ pub enum Expr {
     },
     /// represents a constant in the expressions, e.g., 0, '1', "asdf"
     ConstNum(u64),
+    /// represents a numeric constant of a given type, e.g., `0x1u` or `(uint8_t)0x1`
+    TypedConstNum(u64, Type),
     ConstString(String),
+    /// represents a character literal, e.g., `'a'`
+    ConstChar(char),
+    /// represents adjacent string literals: `"foo" "bar"`
+    StringConcat(Vec<String>),
     ConstBool(bool),
     NewObject {
         name: String,
@@ -53,21 +61,38 @@ pub enum Expr {
     /// represents a function call
     FnCall {
         name: String,
+        /// explicit template arguments, `name<targs>(args)`
+        targs: Vec<TemplateArg>,
         args: Vec<Expr>,
     },
     /// represents a method call
     MethodCall {
         var: Box<Expr>,
         method: String,
+        /// explicit template arguments, `var.method<targs>(args)`
+        targs: Vec<TemplateArg>,
         args: Vec<Expr>,
         is_ptr: bool,
     },
+    /// represents a call through a pointer to member function: `((obj).*(mptr))(args)`
+    MemberPtrCall {
+        obj: Box<Expr>,
+        mptr: Box<Expr>,
+        args: Vec<Expr>,
+    },
     /// represents the dereference operator `*(Expr)`
     Deref(Box<Expr>),
     /// represents the address of operation: `&(Expr)`
     AddrOf(Box<Expr>),
     /// represents the size of operation: `sizeof(Expr)`
     SizeOf(Box<Expr>),
+    /// represents the size of a type: `sizeof(Type)`
+    SizeOfType(Type),
+    /// represents the offset of a field within a type: `offsetof(Type, field)`
+    OffsetOf {
+        ty: Type,
+        field: String,
+    },
     /// accesses the field
     FieldAccess {
         var: Box<Expr>,
@@ -96,10 +121,34 @@ pub enum Expr {
         then: Box<Expr>,
         other: Box<Expr>,
     },
+    /// represents the GNU conditional with omitted operand: `value ?: other`
+    ValueOr {
+        value: Box<Expr>,
+        other: Box<Expr>,
+    },
     Cast {
         expr: Box<Expr>,
         ty: Type,
     },
+    /// reinterprets the bits of the expression as the given type:
+    /// `std::bit_cast<T>(expr)` in C++, a GNU statement expression using `memcpy` in C
+    BitCast {
+        expr: Box<Expr>,
+        ty: Type,
+    },
+    /// represents an initializer list: `{a, b}`
+    InitList(Vec<Expr>),
+    /// represents a zero initializer: `{0}` in C, `{}` in C++
+    ZeroInit,
+    /// represents a designated initializer list: `{.x = a, .y = b}`
+    DesignatedInit(Vec<(String, Expr)>),
+    /// represents a compound literal: `(struct point){.x = 1, .y = 2}`
+    CompoundLiteral {
+        ty: Type,
+        init: Box<Expr>,
+    },
+    /// represents the null pointer constant: `NULL` in C, `nullptr` in C++
+    NullPtr,
     /// represents a raw expression token
     Raw(String),
 }
@@ -109,10 +158,33 @@ impl Expr {
         Expr::ConstString(s.to_string())
     }
 
+    /// creates a concatenation of adjacent string literals
+    pub fn string_concat(parts: &[&str]) -> Self {
+        Expr::StringConcat(parts.iter().map(|p| p.to_string()).collect())
+    }
+
+    /// creates a new character literal
+    ///
+    /// # Example
+    ///
+    /// `a` => `'a'`, `\n` => `'\n'`
+    pub fn new_char(c: char) -> Self {
+        Expr::ConstChar(c)
+    }
+
     pub fn new_num(n: u64) -> Self {
         Expr::ConstNum(n)
     }
 
+    /// creates a numeric constant of the given type, emitting a suffix or a cast
+    ///
+    /// # Example
+    ///
+    /// `1`, `uint32_t` => `0x1u`, `1`, `uint64_t` => `UINT64_C(0x1)`, `1`, `uint8_t` => `(uint8_t)0x1`
+    pub fn new_typed_num(n: u64, ty: Type) -> Self {
+        Expr::TypedConstNum(n, ty)
+    }
+
     pub fn new_var(name: &str, ty: Type) -> Self {
         Expr::Variable {
             name: name.to_string(),
@@ -151,6 +223,58 @@ impl Expr {
         Self::binop(lhs, "&&", rhs)
     }
 
+    /// creates the mask of bit `n` in `reg`, shifting a constant as wide as the
+    /// register, or a 64-bit constant if the width is unknown
+    fn bit_mask(reg: &Expr, n: Expr) -> Self {
+        let ty = match reg.known_type().as_ref().map(|t| t.basetype()) {
+            Some(
+                BaseType::UInt8
+                | BaseType::UInt16
+                | BaseType::UInt32
+                | BaseType::Int8
+                | BaseType::Int16
+                | BaseType::Int32
+                | BaseType::Int
+                | BaseType::Char
+                | BaseType::SignedChar
+                | BaseType::UnsignedChar,
+            ) => Type::new_uint32(),
+            Some(BaseType::Size) => Type::new_size(),
+            Some(BaseType::UIntPtr) => Type::new(BaseType::UIntPtr),
+            _ => Type::new_uint64(),
+        };
+        Self::binop(Expr::new_typed_num(1, ty), "<<", n)
+    }
+
+    /// sets the bit `n` in `reg`
+    ///
+    /// # Example
+    ///
+    /// `uint32_t reg`, `n` => `(reg |= (0x1u << n))`
+    pub fn set_bit(reg: Expr, n: Expr) -> Self {
+        let mask = Self::bit_mask(&reg, n);
+        Self::binop(reg, "|=", mask)
+    }
+
+    /// clears the bit `n` in `reg`
+    ///
+    /// # Example
+    ///
+    /// `uint32_t reg`, `n` => `(reg &= ~((0x1u << n)))`
+    pub fn clear_bit(reg: Expr, n: Expr) -> Self {
+        let mask = Self::uop("~", Self::bit_mask(&reg, n));
+        Self::binop(reg, "&=", mask)
+    }
+
+    /// tests whether the bit `n` in `reg` is set
+    ///
+    /// # Example
+    ///
+    /// `reg`, `n` => `((reg >> n) & 0x1)`
+    pub fn test_bit(reg: Expr, n: Expr) -> Self {
+        Self::binop(Self::binop(reg, ">>", n), "&", Expr::new_num(1))
+    }
+
     pub fn ternary(cond: Expr, then: Expr, other: Expr) -> Self {
         Expr::Ternary {
             cond: Box::new(cond),
@@ -159,6 +283,31 @@ impl Expr {
         }
     }
 
+    /// selects the value if it is non-zero, and the default otherwise
+    ///
+    /// # Example
+    ///
+    /// `x`, `d` => `(x) ? (x) : (d)`
+    pub fn value_or(value: Expr, default: Expr) -> Self {
+        Self::ternary(value.clone(), value, default)
+    }
+
+    /// selects the value if it is non-zero, and the default otherwise, using the GNU
+    /// extension evaluating the value only once
+    ///
+    /// This requires the GNU extensions, see `Scope::set_gnu_extensions`. Otherwise,
+    /// the portable conditional of `Expr::value_or` is emitted, evaluating the value twice.
+    ///
+    /// # Example
+    ///
+    /// `x`, `d` => `(x) ?: (d)`, or `(x) ? (x) : (d)` without GNU extensions
+    pub fn value_or_gnu(value: Expr, default: Expr) -> Self {
+        Expr::ValueOr {
+            value: Box::new(value),
+            other: Box::new(default),
+        }
+    }
+
     pub fn from_fn_param(p: &FunctionParam) -> Self {
         p.to_expr()
     }
@@ -178,11 +327,27 @@ impl Expr {
         }
     }
 
+    /// creates the null pointer constant, `NULL` in C and `nullptr` in C++
     pub fn null() -> Self {
-        Expr::Variable {
-            name: "NULL".to_string(),
-            ty: Type::to_ptr(&Type::new(BaseType::Void)),
-        }
+        Expr::NullPtr
+    }
+
+    /// compares the expression against the null pointer constant of the dialect
+    ///
+    /// # Example
+    ///
+    /// `p` => `(p == NULL)` or `(p == nullptr)`
+    pub fn is_null(e: Expr) -> Self {
+        Expr::binop(e, "==", Expr::NullPtr)
+    }
+
+    /// tests the expression for not being the null pointer constant of the dialect
+    ///
+    /// # Example
+    ///
+    /// `p` => `(p != NULL)` or `(p != nullptr)`
+    pub fn not_null(e: Expr) -> Self {
+        Expr::binop(e, "!=", Expr::NullPtr)
     }
 
     pub fn new(class: &str, args: Vec<Expr>) -> Self {
@@ -204,10 +369,53 @@ impl Expr {
         Expr::SizeOf(Box::new(self.clone()))
     }
 
+    /// creates an expression for the size of the type
+    ///
+    /// # Example
+    ///
+    /// `union foo` => `sizeof(union foo)`, `uint8_t[4]` => `sizeof(uint8_t[4])`
+    pub fn size_of_type(ty: Type) -> Self {
+        Expr::SizeOfType(ty)
+    }
+
+    /// creates an expression for the number of elements of a static array
+    ///
+    /// # Example
+    ///
+    /// `arr` => `(sizeof(arr) / sizeof(arr[0x0]))`
+    pub fn array_len(array: Expr) -> Self {
+        let elem = Expr::array_access(&array, &Expr::new_num(0));
+        Self::binop(array.size_of(), "/", elem.size_of())
+    }
+
+    /// creates an expression for the offset of the field within the type
+    ///
+    /// # Example
+    ///
+    /// `struct foo`, `bar` => `offsetof(struct foo, bar)`
+    pub fn offset_of(ty: Type, field: &str) -> Self {
+        Expr::OffsetOf {
+            ty,
+            field: String::from(field),
+        }
+    }
+
     pub fn deref(&self) -> Self {
         Expr::Deref(Box::new(self.clone()))
     }
 
+    /// dereferences the expression if it is a pointer, mirroring `Type::to_deref`
+    ///
+    /// # Example
+    ///
+    /// `p` => `Some(*p)`, `0x5` => `None`
+    pub fn try_deref(&self) -> Option<Self> {
+        if !self.is_ptr() {
+            return None;
+        }
+        Some(self.deref())
+    }
+
     pub fn field_access(&self, field: &str) -> Self {
         Expr::FieldAccess {
             var: Box::new(self.clone()),
@@ -226,18 +434,57 @@ impl Expr {
 
     /// TODO: add type information here!
     pub fn method_call(var: &Expr, method: &str, args: Vec<Expr>) -> Self {
-        Expr::MethodCall {
-            var: Box::new(var.clone()),
-            method: method.to_string(),
+        Self::template_method_call(var, method, Vec::new(), args)
+    }
+
+    /// calls the pointer to member function `mptr` on the object, using `->*` if the
+    /// object is a pointer
+    ///
+    /// # Example
+    ///
+    /// `obj`, `mptr` => `((obj).*(mptr))(args)`, `p`, `mptr` => `((p)->*(mptr))(args)`
+    pub fn member_ptr_call(obj: &Expr, mptr: &Expr, args: Vec<Expr>) -> Self {
+        Expr::MemberPtrCall {
+            obj: Box::new(obj.clone()),
+            mptr: Box::new(mptr.clone()),
             args,
-            is_ptr: false,
         }
     }
 
     pub fn fn_call(name: &str, args: Vec<Expr>) -> Self {
+        Self::template_fn_call(name, Vec::new(), args)
+    }
+
+    /// calls the function with explicit template arguments
+    ///
+    /// # Example
+    ///
+    /// `foo`, `[int]`, `[x]` => `foo<int>(x)`
+    pub fn template_fn_call(name: &str, targs: Vec<TemplateArg>, args: Vec<Expr>) -> Self {
         Expr::FnCall {
             name: String::from(name),
+            targs,
+            args,
+        }
+    }
+
+    /// calls the method with explicit template arguments
+    ///
+    /// # Example
+    ///
+    /// `obj`, `get`, `[T]` => `obj.get<T>()`
+    pub fn template_method_call(
+        var: &Expr,
+        method: &str,
+        targs: Vec<TemplateArg>,
+        args: Vec<Expr>,
+    ) -> Self {
+        Expr::MethodCall {
+            var: Box::new(var.clone()),
+            method: method.to_string(),
+            targs,
             args,
+            is_ptr: false,
         }
     }
 
@@ -248,6 +495,60 @@ impl Expr {
         }
     }
 
+    /// computes the address `off` bytes past `base` as a pointer of type `ty`
+    ///
+    /// The base is cast to `char *` so the offset is applied in bytes.
+    ///
+    /// # Example
+    ///
+    /// `base`, `off`, `uint32_t *` => `(uint32_t *)(((char *)(base) + off))`
+    pub fn byte_offset(base: Expr, off: Expr, ty: Type) -> Self {
+        let base = base.cast_to(Type::new_char().to_ptr());
+        Self::binop(base, "+", off).cast_to(ty)
+    }
+
+    /// reinterprets the bits of the value as the given type
+    ///
+    /// In C with the GNU extensions, see `Scope::set_gnu_extensions`, this uses a statement
+    /// expression copying the value with `memcpy`. Otherwise, the value is punned through
+    /// a union, which requires the type of the value to be known; if it is not, the
+    /// statement expression is used regardless. `Block::bit_cast` is the portable
+    /// alternative.
+    ///
+    /// # Example
+    ///
+    /// `x`, `uint32_t` => `std::bit_cast<uint32_t>(x)` (C++)
+    ///
+    /// `x`, `uint32_t` => `({ __typeof__(x) bit_cast_src = (x); uint32_t bit_cast_dst; ...; bit_cast_dst; })` (GNU C)
+    ///
+    /// `x: float`, `uint32_t` => `((union { float bit_cast_src; uint32_t bit_cast_dst; }){ .bit_cast_src = (x) }).bit_cast_dst` (C)
+    pub fn bit_cast(value: &Expr, ty: Type) -> Self {
+        Expr::BitCast {
+            expr: Box::new(value.clone()),
+            ty,
+        }
+    }
+
+    /// creates an initializer list from the given expressions
+    pub fn init_list(elems: Vec<Expr>) -> Self {
+        Expr::InitList(elems)
+    }
+
+    /// creates an initializer that zero-initializes the object, `{0}` in C, `{}` in C++
+    pub fn zero_init() -> Self {
+        Expr::ZeroInit
+    }
+
+    /// creates a designated initializer list from the given field/value pairs
+    pub fn designated_init(fields: Vec<(&str, Expr)>) -> Self {
+        Expr::DesignatedInit(fields.into_iter().map(|(f, e)| (f.to_string(), e)).collect())
+    }
+
+    /// creates a compound literal of the given type and initializer
+    pub fn compound_literal(ty: Type, init: Expr) -> Self {
+        Expr::CompoundLiteral { ty, init: Box::new(init) }
+    }
+
     pub fn set_ptr(&mut self) {
         match self {
             Expr::MethodCall { is_ptr, .. } => {
@@ -268,12 +569,112 @@ impl Expr {
             Expr::Variable { ty, .. } => ty.is_ptr(),
             Expr::Deref(e) => e.is_ptr(),
             Expr::AddrOf(_) => true,
+            Expr::NullPtr => true,
             Expr::Raw(_) => true,
             Expr::NewObject { .. } => true,
             Expr::MethodCall { is_ptr, .. } => *is_ptr,
             Expr::FieldAccess { is_ptr, .. } => *is_ptr,
             Expr::ArrayElementAccess { is_ptr, .. } => *is_ptr,
             Expr::Cast { ty, .. } => ty.is_ptr(),
+            Expr::BitCast { ty, .. } => ty.is_ptr(),
+            _ => false,
+        }
+    }
+
+    /// obtains the type of the expression, if it is known
+    ///
+    /// # Example
+    ///
+    /// `(size_t)(x)` => `Some(size_t)`, `0x5` => `None`
+    pub(crate) fn known_type(&self) -> Option<Type> {
+        match self {
+            Expr::Variable { ty, .. } => Some(ty.clone()),
+            Expr::TypedConstNum(_, ty) => Some(ty.clone()),
+            Expr::Cast { ty, .. } | Expr::BitCast { ty, .. } => Some(ty.clone()),
+            Expr::CompoundLiteral { ty, .. } => Some(ty.clone()),
+            _ => None,
+        }
+    }
+
+    /// obtains the direct sub-expressions of the expression
+    fn operands(&self) -> Vec<&Expr> {
+        match self {
+            Expr::NewObject { args, .. } | Expr::FnCall { args, .. } => args.iter().collect(),
+            Expr::MethodCall { var, args, .. } => {
+                let mut ret = vec![var.as_ref()];
+                ret.extend(args.iter());
+                ret
+            }
+            Expr::MemberPtrCall { obj, mptr, args } => {
+                let mut ret = vec![obj.as_ref(), mptr.as_ref()];
+                ret.extend(args.iter());
+                ret
+            }
+            Expr::DeleteObject { var: e }
+            | Expr::Deref(e)
+            | Expr::AddrOf(e)
+            | Expr::SizeOf(e)
+            | Expr::FieldAccess { var: e, .. }
+            | Expr::UnOp { expr: e, .. }
+            | Expr::Cast { expr: e, .. }
+            | Expr::BitCast { expr: e, .. }
+            | Expr::CompoundLiteral { init: e, .. } => vec![e.as_ref()],
+            Expr::ArrayElementAccess { var, idx, .. } => vec![var.as_ref(), idx.as_ref()],
+            Expr::BinOp { lhs, rhs, .. } => vec![lhs.as_ref(), rhs.as_ref()],
+            Expr::Ternary { cond, then, other } => {
+                vec![cond.as_ref(), then.as_ref(), other.as_ref()]
+            }
+            Expr::ValueOr { value, other } => vec![value.as_ref(), other.as_ref()],
+            Expr::InitList(elems) => elems.iter().collect(),
+            Expr::DesignatedInit(fields) => fields.iter().map(|(_, e)| e).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// obtains the system includes required by the expression in the dialect, e.g.,
+    /// `string.h` for the `memcpy` of a bit cast, or `stdlib.h` for a call to `malloc`
+    pub(crate) fn required_includes(&self, dialect: Dialect, incs: &mut Vec<&'static str>) {
+        match self {
+            Expr::BitCast { .. } if dialect.is_cpp() => add_include(incs, dialect, "bit"),
+            Expr::BitCast { .. } => add_include(incs, dialect, "string.h"),
+            Expr::TypedConstNum(_, ty)
+                if !ty.is_ptr() && matches!(ty.basetype(), BaseType::Int64 | BaseType::UInt64) =>
+            {
+                add_include(incs, dialect, "stdint.h")
+            }
+            Expr::FnCall { name, .. } => {
+                if let Some(hdr) = fn_call_include(name) {
+                    add_include(incs, dialect, hdr);
+                }
+            }
+            _ => (),
+        }
+        for e in self.operands() {
+            e.required_includes(dialect, incs);
+        }
+    }
+
+    /// tests whether the expression may be used as an integral constant expression,
+    /// e.g., as a case label
+    ///
+    /// Variables of integer or enum type and raw tokens are accepted as they may refer
+    /// to enumerators or macros, and casts to enum and (non-pointer) typedef types are
+    /// accepted as well.
+    pub fn is_integral_const(&self) -> bool {
+        match self {
+            Expr::ConstNum(_) | Expr::ConstChar(_) | Expr::ConstBool(_) => true,
+            Expr::TypedConstNum(_, ty) => ty.is_integer() || ty.is_enum(),
+            Expr::Variable { ty, .. } => ty.is_integer() || ty.is_enum(),
+            Expr::Raw(_) => true,
+            Expr::SizeOf(_) | Expr::SizeOfType(_) | Expr::OffsetOf { .. } => true,
+            Expr::BinOp { lhs, rhs, .. } => lhs.is_integral_const() && rhs.is_integral_const(),
+            Expr::UnOp { expr, .. } => expr.is_integral_const(),
+            Expr::Cast { expr, ty } => {
+                (ty.is_integer() || ty.is_enum()) && expr.is_integral_const()
+            }
+            Expr::Ternary { cond, then, other } => {
+                cond.is_integral_const() && then.is_integral_const() && other.is_integral_const()
+            }
             _ => false,
         }
     }
@@ -282,21 +683,112 @@ impl Expr {
         match self {
             Expr::Variable { ty, .. } => ty.is_struct(),
             Expr::Cast { ty, .. } => ty.is_struct(),
+            Expr::CompoundLiteral { ty, .. } => ty.is_struct(),
             Expr::NewObject { .. } => true,
             Expr::Raw(_) => true,
             _ => false,
         }
     }
 
+    /// tests whether the expression needs parentheses when used as the operand of
+    /// a prefix operator such as `*` or `&`
+    fn needs_parens(&self) -> bool {
+        !matches!(
+            self,
+            Expr::Variable { .. }
+                | Expr::ConstNum(_)
+                | Expr::ConstString(_)
+                | Expr::ConstChar(_)
+                | Expr::ConstBool(_)
+                | Expr::NullPtr
+                | Expr::FnCall { .. }
+                | Expr::MethodCall { .. }
+                | Expr::MemberPtrCall { .. }
+                | Expr::BitCast { .. }
+                | Expr::Deref(_)
+                | Expr::AddrOf(_)
+                | Expr::SizeOf(_)
+                | Expr::SizeOfType(_)
+                | Expr::OffsetOf { .. }
+                | Expr::FieldAccess { .. }
+                | Expr::ArrayElementAccess { .. }
+                // binary operations are already parenthesized
+                | Expr::BinOp { .. }
+                | Expr::UnOp { .. }
+        )
+    }
+
+    /// formats the explicit template arguments of a call, if any
+    fn fmt_targs(targs: &[TemplateArg], fmt: &mut Formatter<'_>) -> fmt::Result {
+        if targs.is_empty() {
+            return Ok(());
+        }
+        write!(fmt, "<")?;
+        for (i, t) in targs.iter().enumerate() {
+            if i != 0 {
+                write!(fmt, ", ")?;
+            }
+            t.fmt(fmt)?;
+        }
+        write!(fmt, ">")
+    }
+
+    /// formats the expression as the operand of a prefix operator
+    fn fmt_operand(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if self.needs_parens() {
+            write!(fmt, "(")?;
+            self.fmt(fmt)?;
+            write!(fmt, ")")
+        } else {
+            self.fmt(fmt)
+        }
+    }
+
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Expr::Variable { name, .. } => write!(fmt, "{name}"),
             Expr::ConstString(x) => write!(fmt, "\"{x}\""),
+            Expr::StringConcat(parts) => {
+                for (i, p) in parts.iter().enumerate() {
+                    if i != 0 {
+                        write!(fmt, " ")?;
+                    }
+                    write!(fmt, "\"{}\"", escape_str(p))?;
+                }
+                Ok(())
+            }
+            Expr::ConstChar(c) => match c {
+                '\'' => write!(fmt, "'\\''"),
+                '\0' => write!(fmt, "'\\0'"),
+                '"' => write!(fmt, "'\"'"),
+                _ => write!(fmt, "'{}'", escape_str(&c.to_string())),
+            },
             Expr::ConstNum(x) => write!(fmt, "0x{x:x}"),
+            Expr::TypedConstNum(x, ty) => match ty.basetype() {
+                _ if ty.is_ptr() => {
+                    write!(fmt, "(")?;
+                    ty.fmt(fmt)?;
+                    write!(fmt, ")0x{x:x}")
+                }
+                BaseType::Int | BaseType::Int32 => write!(fmt, "0x{x:x}"),
+                BaseType::UInt32 => write!(fmt, "0x{x:x}u"),
+                // `long` and `long long` differ in width between data models
+                BaseType::Int64 => write!(fmt, "INT64_C(0x{x:x})"),
+                BaseType::UInt64 => write!(fmt, "UINT64_C(0x{x:x})"),
+                _ => {
+                    write!(fmt, "(")?;
+                    ty.fmt(fmt)?;
+                    write!(fmt, ")0x{x:x}")
+                }
+            },
+            Expr::NullPtr if fmt.dialect().is_cpp() => write!(fmt, "nullptr"),
+            Expr::NullPtr => write!(fmt, "NULL"),
             Expr::ConstBool(true) => write!(fmt, "true"),
             Expr::ConstBool(false) => write!(fmt, "false"),
-            Expr::FnCall { name, args } => {
-                write!(fmt, "{name}(")?;
+            Expr::FnCall { name, targs, args } => {
+                write!(fmt, "{name}")?;
+                Self::fmt_targs(targs, fmt)?;
+                write!(fmt, "(")?;
                 for (i, v) in args.iter().enumerate() {
                     if i != 0 {
                         write!(fmt, ", ")?;
@@ -306,22 +798,35 @@ impl Expr {
                 write!(fmt, ")")
             }
             Expr::Deref(e) => {
-                write!(fmt, "*(")?;
-                e.as_ref().fmt(fmt)?;
-                write!(fmt, ")")
+                write!(fmt, "*")?;
+                e.as_ref().fmt_operand(fmt)
             }
             Expr::AddrOf(e) => {
-                write!(fmt, "&(")?;
-                e.as_ref().fmt(fmt)?;
-                write!(fmt, ")")
+                write!(fmt, "&")?;
+                e.as_ref().fmt_operand(fmt)
             }
             Expr::SizeOf(e) => {
                 write!(fmt, "sizeof(")?;
                 e.as_ref().fmt(fmt)?;
                 write!(fmt, ")")
             }
+            Expr::SizeOfType(ty) => {
+                write!(fmt, "sizeof(")?;
+                ty.fmt(fmt)?;
+                if ty.is_array() {
+                    write!(fmt, "[{}]", ty.get_array_size())?;
+                }
+                write!(fmt, ")")
+            }
+            Expr::OffsetOf { ty, field } => {
+                write!(fmt, "offsetof(")?;
+                ty.fmt(fmt)?;
+                write!(fmt, ", {field})")
+            }
             Expr::FieldAccess { var, field, .. } => {
-                write!(fmt, "({})", var.as_ref())?;
+                write!(fmt, "(")?;
+                var.as_ref().fmt(fmt)?;
+                write!(fmt, ")")?;
                 if var.is_ptr() {
                     write!(fmt, "->{field}")
                 } else {
@@ -330,15 +835,39 @@ impl Expr {
             }
             Expr::ArrayElementAccess { var, idx, is_ptr: _ } => {
                 var.as_ref().fmt(fmt)?;
-                write!(fmt, "[{idx}]")
+                write!(fmt, "[")?;
+                idx.as_ref().fmt(fmt)?;
+                write!(fmt, "]")
             }
-            Expr::MethodCall { var, method, args, .. } => {
+            Expr::MethodCall {
+                var, method, targs, args, ..
+            } => {
                 var.as_ref().fmt(fmt)?;
                 if var.is_ptr() {
-                    write!(fmt, "->{method}(")?;
+                    write!(fmt, "->{method}")?;
+                } else {
+                    write!(fmt, ".{method}")?;
+                }
+                Self::fmt_targs(targs, fmt)?;
+                write!(fmt, "(")?;
+                for (i, v) in args.iter().enumerate() {
+                    if i != 0 {
+                        write!(fmt, ", ")?;
+                    }
+                    v.fmt(fmt)?;
+                }
+                write!(fmt, ")")
+            }
+            Expr::MemberPtrCall { obj, mptr, args } => {
+                write!(fmt, "((")?;
+                obj.as_ref().fmt(fmt)?;
+                if obj.is_ptr() {
+                    write!(fmt, ")->*(")?;
                 } else {
-                    write!(fmt, ".{method}(")?;
+                    write!(fmt, ").*(")?;
                 }
+                mptr.as_ref().fmt(fmt)?;
+                write!(fmt, "))(")?;
                 for (i, v) in args.iter().enumerate() {
                     if i != 0 {
                         write!(fmt, ", ")?;
@@ -368,27 +897,136 @@ impl Expr {
                 other.as_ref().fmt(fmt)?;
                 write!(fmt, ")")
             }
+            Expr::ValueOr { value, other } if !fmt.gnu_extensions() => {
+                Expr::value_or(value.as_ref().clone(), other.as_ref().clone()).fmt(fmt)
+            }
+            Expr::ValueOr { value, other } => {
+                write!(fmt, "(")?;
+                value.as_ref().fmt(fmt)?;
+                write!(fmt, ") ?: (")?;
+                other.as_ref().fmt(fmt)?;
+                write!(fmt, ")")
+            }
             Expr::NewObject { name, args } => {
                 write!(fmt, "new {}(", name)?;
                 for (i, arg) in args.iter().enumerate() {
                     if i > 0 {
                         write!(fmt, ", ")?;
                     }
-                    write!(fmt, "{}", arg)?;
+                    arg.fmt(fmt)?;
                 }
                 write!(fmt, ")")
             }
             Expr::DeleteObject { var } => {
-                write!(fmt, "delete[] {}", var)
+                write!(fmt, "delete[] ")?;
+                var.as_ref().fmt(fmt)
             }
             Expr::Cast { expr, ty } => {
-                write!(fmt, "({ty})({expr})")
+                write!(fmt, "(")?;
+                ty.fmt(fmt)?;
+                write!(fmt, ")(")?;
+                expr.as_ref().fmt(fmt)?;
+                write!(fmt, ")")
+            }
+            Expr::BitCast { expr, ty } if fmt.dialect().is_cpp() => {
+                write!(fmt, "std::bit_cast<")?;
+                ty.fmt(fmt)?;
+                write!(fmt, ">(")?;
+                expr.as_ref().fmt(fmt)?;
+                write!(fmt, ")")
+            }
+            Expr::BitCast { expr, ty } if !fmt.gnu_extensions() && expr.known_type().is_some() => {
+                let src = expr.known_type().unwrap();
+                write!(fmt, "((union {{ ")?;
+                src.fmt_with_name(fmt, "bit_cast_src")?;
+                write!(fmt, "; ")?;
+                ty.fmt_with_name(fmt, "bit_cast_dst")?;
+                write!(fmt, "; }}){{ .bit_cast_src = (")?;
+                expr.as_ref().fmt(fmt)?;
+                write!(fmt, ") }}).bit_cast_dst")
+            }
+            Expr::BitCast { expr, ty } => {
+                // copy through a temporary, so the value does not need to be an lvalue
+                write!(fmt, "({{ __typeof__(")?;
+                expr.as_ref().fmt(fmt)?;
+                write!(fmt, ") bit_cast_src = (")?;
+                expr.as_ref().fmt(fmt)?;
+                write!(fmt, "); ")?;
+                ty.fmt_with_name(fmt, "bit_cast_dst")?;
+                write!(fmt, "; memcpy(&bit_cast_dst, &bit_cast_src, sizeof(bit_cast_dst)); ")?;
+                write!(fmt, "bit_cast_dst; }})")
+            }
+            Expr::InitList(elems) => {
+                write!(fmt, "{{")?;
+                for (i, v) in elems.iter().enumerate() {
+                    if i != 0 {
+                        write!(fmt, ", ")?;
+                    }
+                    v.fmt(fmt)?;
+                }
+                write!(fmt, "}}")
+            }
+            Expr::ZeroInit if fmt.dialect().is_cpp() => write!(fmt, "{{}}"),
+            Expr::ZeroInit => write!(fmt, "{{0}}"),
+            Expr::DesignatedInit(fields) => {
+                write!(fmt, "{{")?;
+                for (i, (f, v)) in fields.iter().enumerate() {
+                    if i != 0 {
+                        write!(fmt, ", ")?;
+                    }
+                    write!(fmt, ".{f} = ")?;
+                    v.fmt(fmt)?;
+                }
+                write!(fmt, "}}")
+            }
+            Expr::CompoundLiteral { ty, init } => {
+                write!(fmt, "(")?;
+                ty.fmt(fmt)?;
+                write!(fmt, ")")?;
+                init.as_ref().fmt(fmt)
             }
             Expr::Raw(s) => write!(fmt, "{s}"),
         }
     }
 }
 
+/// obtains the C standard header declaring the library function, if any
+///
+/// # Example
+///
+/// `malloc` => `stdlib.h`, `memcpy` => `string.h`
+pub(crate) fn fn_call_include(name: &str) -> Option<&'static str> {
+    match name {
+        "malloc" | "calloc" | "realloc" | "free" => Some("stdlib.h"),
+        "memcpy" | "memmove" | "memset" | "memcmp" => Some("string.h"),
+        _ => None,
+    }
+}
+
+/// adds the standard header in the spelling of the dialect, unless it is included already
+pub(crate) fn add_include(incs: &mut Vec<&'static str>, dialect: Dialect, hdr: &'static str) {
+    let hdr = dialect.std_header(hdr);
+    if !incs.contains(&hdr) {
+        incs.push(hdr);
+    }
+}
+
+/// escapes the string so it can be used inside a C string literal
+pub(crate) fn escape_str(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c => ret.push(c),
+        }
+    }
+    ret
+}
+
 impl Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut ret = String::new();
@@ -46,6 +46,9 @@ pub struct Field {
 
     /// The documentation comment of the variant
     doc: Option<Doc>,
+
+    /// a short comment placed after the field declaration
+    trailing_comment: Option<String>,
 }
 
 impl Field {
@@ -61,9 +64,21 @@ impl Field {
             ty,
             width: None,
             doc: None,
+            trailing_comment: None,
         }
     }
 
+    /// Creates a new unnamed bitfield used for padding, or alignment if the width is 0
+    ///
+    /// # Example
+    ///
+    /// `uint32_t : 3;`
+    pub fn new_unnamed_bitfield(ty: Type, width: u8) -> Self {
+        let mut f = Field::with_string(String::new(), ty);
+        f.width = Some(width);
+        f
+    }
+
     /// obtains the name of the field
     pub fn name(&self) -> &str {
         &self.name
@@ -111,20 +126,63 @@ impl Field {
         self.width.is_some()
     }
 
-    /// Formats the variant using the given formatter.
-    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        if let Some(ref docs) = self.doc {
-            docs.fmt(fmt)?;
-        }
-        self.ty.fmt(fmt)?;
-        write!(fmt, " {}", self.name)?;
+    /// obtains the width of the bitfield, if this is a bitfield
+    pub fn bitfield_width(&self) -> Option<u8> {
+        self.width
+    }
+
+    /// sets a trailing comment that is placed after the field declaration
+    ///
+    /// # Example
+    ///
+    /// `int foo;`  => `int foo; // comment`
+    pub fn trailing_comment(&mut self, comment: &str) -> &mut Self {
+        self.trailing_comment = Some(String::from(comment));
+        self
+    }
+
+    /// formats the declaration of the field without documentation or comments
+    fn fmt_decl(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.ty.fmt_with_name(fmt, &self.name)?;
         if let Some(w) = self.width {
             write!(fmt, " : {w}")?;
         }
         if self.ty.is_array() {
             write!(fmt, "[{}]", self.ty.get_array_size())?;
         }
-        writeln!(fmt, ";")
+        write!(fmt, ";")
+    }
+
+    /// returns the number of characters of the field declaration
+    pub(crate) fn decl_width(&self) -> usize {
+        let mut ret = String::new();
+        self.fmt_decl(&mut Formatter::new(&mut ret)).unwrap();
+        ret.chars().count()
+    }
+
+    /// formats the field, aligning the trailing comment to the given column
+    pub(crate) fn do_fmt(
+        &self,
+        fmt: &mut Formatter<'_>,
+        comment_col: Option<usize>,
+    ) -> fmt::Result {
+        if let Some(ref docs) = self.doc {
+            docs.fmt(fmt)?;
+        }
+        self.fmt_decl(fmt)?;
+        if let Some(c) = &self.trailing_comment {
+            match comment_col {
+                Some(col) => fmt.pad_to(col)?,
+                None => write!(fmt, " ")?,
+            }
+            write!(fmt, "// {c}")?;
+        }
+        writeln!(fmt)
+    }
+
+    /// Formats the variant using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.do_fmt(fmt, None)
     }
 }
 
@@ -33,9 +33,34 @@
 // the standard formatter types
 use std::fmt::{self, Write};
 
+use crate::{Dialect, TemplateArg};
+
 /// defines the default indentation level
 const DEFAULT_INDENT: usize = 4;
 
+/// defines the default column limit of the generated code
+pub(crate) const DEFAULT_LINE_WIDTH: usize = 100;
+
+/// the line ending of the generated code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// Unix line endings, `\n`
+    #[default]
+    Lf,
+    /// Windows line endings, `\r\n`
+    Crlf,
+}
+
+impl LineEnding {
+    /// obtains the character sequence terminating a line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
 /// Formatter for a scope.
 #[derive(Debug)]
 pub struct Formatter<'a> {
@@ -47,15 +72,111 @@ pub struct Formatter<'a> {
 
     /// the current scope
     scope: Vec<String>,
+
+    /// the language dialect of the generated code
+    dialect: Dialect,
+
+    /// the line ending emitted for every new line
+    line_ending: LineEnding,
+
+    /// whether preprocessor directives are emitted at column 0 regardless of indentation
+    left_align_directives: bool,
+
+    /// whether deleted functions carry their reason, `= delete("reason")` (C++26)
+    delete_reasons: bool,
+
+    /// whether GNU extensions may be used, e.g., statement expressions
+    gnu_extensions: bool,
+
+    /// the column limit of the generated code, e.g., for heading separators
+    line_width: usize,
 }
 
 impl<'a> Formatter<'a> {
     /// Returns a new formatter instance.
     pub fn new(dst: &'a mut String) -> Self {
+        Self::with_dialect(dst, Dialect::default())
+    }
+
+    /// Returns a new formatter instance emitting code in the given dialect.
+    pub fn with_dialect(dst: &'a mut String, dialect: Dialect) -> Self {
         Self {
             dst,
             spaces: 0,
             scope: vec![],
+            dialect,
+            line_ending: LineEnding::default(),
+            left_align_directives: false,
+            delete_reasons: false,
+            gnu_extensions: false,
+            line_width: DEFAULT_LINE_WIDTH,
+        }
+    }
+
+    /// sets the line ending emitted for every new line
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// sets whether preprocessor directives are emitted at column 0
+    ///
+    /// A line is a directive if it starts with `#` followed by a directive name,
+    /// e.g., `#ifdef` or `# pragma`, see `is_directive`.
+    pub fn with_left_aligned_directives(mut self, val: bool) -> Self {
+        self.left_align_directives = val;
+        self
+    }
+
+    /// sets whether deleted functions carry their reason, `= delete("reason")`
+    pub fn with_delete_reasons(mut self, val: bool) -> Self {
+        self.delete_reasons = val;
+        self
+    }
+
+    /// sets whether GNU extensions may be used, e.g., statement expressions
+    pub fn with_gnu_extensions(mut self, val: bool) -> Self {
+        self.gnu_extensions = val;
+        self
+    }
+
+    /// sets the column limit of the generated code
+    pub fn with_line_width(mut self, width: usize) -> Self {
+        self.line_width = width;
+        self
+    }
+
+    /// returns the column limit of the generated code
+    pub fn line_width(&self) -> usize {
+        self.line_width
+    }
+
+    /// returns the language dialect of the generated code
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    /// returns whether deleted functions carry their reason
+    pub fn delete_reasons(&self) -> bool {
+        self.delete_reasons
+    }
+
+    /// returns whether GNU extensions may be used
+    pub fn gnu_extensions(&self) -> bool {
+        self.gnu_extensions
+    }
+
+    /// writes the `__declspec(spec)` of a declaration followed by a space
+    ///
+    /// Only MSVC knows `__declspec`. In the other dialects, `dllexport` gives the symbol
+    /// the default visibility, `dllimport` is omitted as the symbols of shared libraries
+    /// are imported anyway, and other specifiers, e.g., `noinline`, become GNU attributes.
+    pub(crate) fn write_declspec(&mut self, spec: &str) -> fmt::Result {
+        match (self.dialect, spec) {
+            (Dialect::Msvc, _) => write!(self, "__declspec({spec}) "),
+            (_, "dllexport") => write!(self, "__attribute__((visibility(\"default\"))) "),
+            (_, "dllimport") => Ok(()),
+            _ => write!(self, "__attribute__(({spec})) "),
         }
     }
 
@@ -75,11 +196,27 @@ impl<'a> Formatter<'a> {
 
     pub fn write_scoped_name(&mut self, name: &str) -> fmt::Result {
         write!(self, " ")?;
-        for s in &self.scope {
-            self.dst.push_str(s);
-            self.dst.push_str("::");
+        self.write_scoped(name)
+    }
+
+    /// writes the name qualified with the current scope, without a leading space
+    pub fn write_scoped(&mut self, name: &str) -> fmt::Result {
+        self.write_scoped_specialized(name, &[])
+    }
+
+    /// writes the name qualified with the current scope, where the innermost scope is
+    /// specialized with the given template arguments, e.g., `Foo<int>::bar`
+    pub fn write_scoped_specialized(&mut self, name: &str, args: &[TemplateArg]) -> fmt::Result {
+        let mut scoped = String::new();
+        for (i, s) in self.scope.iter().enumerate() {
+            scoped.push_str(s);
+            if i + 1 == self.scope.len() && !args.is_empty() {
+                let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+                scoped.push_str(&format!("<{}>", args.join(", ")));
+            }
+            scoped.push_str("::");
         }
-        write!(self, "{name}")
+        write!(self, "{scoped}{name}")
     }
 
     /// Wraps the given function in a a C block. { ...}
@@ -113,6 +250,19 @@ impl<'a> Formatter<'a> {
         self.dst.is_empty() || self.dst.ends_with('\n')
     }
 
+    /// returns the current column, i.e., the number of characters since the last new line
+    pub fn column(&self) -> usize {
+        self.dst.rsplit('\n').next().map(|l| l.chars().count()).unwrap_or(0)
+    }
+
+    /// pads the current line with spaces until it reaches the given column
+    pub fn pad_to(&mut self, col: usize) -> fmt::Result {
+        for _ in self.column()..col {
+            self.dst.push(' ');
+        }
+        Ok(())
+    }
+
     /// writes spaces into the destination buffer
     fn push_spaces(&mut self) {
         for _ in 0..self.spaces {
@@ -128,12 +278,15 @@ impl fmt::Write for Formatter<'_> {
 
         for line in s.lines() {
             if !first {
-                self.dst.push('\n');
+                self.dst.push_str(self.line_ending.as_str());
             }
 
             first = false;
 
-            let do_indent = should_indent && !line.is_empty() && line.as_bytes()[0] != b'\n';
+            let do_indent = should_indent
+                && !line.is_empty()
+                && line.as_bytes()[0] != b'\n'
+                && !(self.left_align_directives && is_directive(line));
 
             if do_indent {
                 self.push_spaces();
@@ -146,9 +299,25 @@ impl fmt::Write for Formatter<'_> {
         }
 
         if s.as_bytes().last() == Some(&b'\n') {
-            self.dst.push('\n');
+            self.dst.push_str(self.line_ending.as_str());
         }
 
         Ok(())
     }
 }
+
+/// tests whether the line is a preprocessor directive, e.g., `#ifdef FOO` or `# define X`
+///
+/// Other lines starting with `#`, e.g., the stringification `#x` in a macro body,
+/// are not directives.
+fn is_directive(line: &str) -> bool {
+    const DIRECTIVES: &[&str] = &[
+        "if", "ifdef", "ifndef", "elif", "elifdef", "elifndef", "else", "endif", "define", "undef",
+        "include", "pragma", "error", "warning", "line", "embed",
+    ];
+    let Some(rest) = line.strip_prefix('#') else {
+        return false;
+    };
+    let mut name = rest.trim_start().split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'));
+    DIRECTIVES.contains(&name.next().unwrap_or_default())
+}
@@ -30,7 +30,7 @@
 
 use std::fmt::{self, Display, Write};
 
-use crate::{Block, Doc, Formatter, FunctionParam, Type};
+use crate::{AttributeSpec, BaseType, Block, Doc, Expr, Formatter, FunctionParam, Type};
 
 /// defines a C function
 #[derive(Debug, Clone)]
@@ -48,7 +48,7 @@ pub struct Function {
     ret: Type,
 
     /// attributes of the function
-    attributes: Vec<String>,
+    attributes: Vec<AttributeSpec>,
 
     /// whether the function is static
     is_static: bool,
@@ -59,6 +59,24 @@ pub struct Function {
     /// whether the function is extern
     is_extern: bool,
 
+    /// whether the function has C language linkage, `extern "C"`
+    is_extern_c: bool,
+
+    /// whether the function never returns
+    is_noreturn: bool,
+
+    /// the calling convention of the function, e.g., `__stdcall`
+    calling_convention: Option<String>,
+
+    /// the `__declspec` of the function, e.g., `dllexport`
+    declspec: Option<String>,
+
+    /// the macro selecting the DLL storage class, see `Scope::new_export_macro`
+    export_macro: Option<String>,
+
+    /// whether only the declaration of the function is emitted
+    is_decl_only: bool,
+
     /// the body of the function, a sequence of statements
     body: Block,
 }
@@ -80,6 +98,12 @@ impl Function {
             is_static: false,
             is_inline: false,
             is_extern: false,
+            is_extern_c: false,
+            is_noreturn: false,
+            calling_convention: None,
+            declspec: None,
+            export_macro: None,
+            is_decl_only: false,
             body: Block::new(),
         }
     }
@@ -89,9 +113,14 @@ impl Function {
         &self.name
     }
 
-    /// obtains the type for this function
+    /// obtains the type for this function, i.e., the decayed function pointer type
+    ///
+    /// # Example
+    ///
+    /// `int foo(int a)` => `int (*)(int)`
     pub fn to_type(&self) -> Type {
-        panic!("needs to implement a corresponding type.")
+        let params = self.params.iter().map(|p| p.to_type()).collect();
+        Type::new_fn_ptr(self.ret.clone(), params)
     }
 
     /// obtains a type reference of the return type
@@ -147,9 +176,18 @@ impl Function {
         self.params.get_mut(idx)
     }
 
-    /// adds a new attribute to the function
+    /// adds a new GNU attribute to the function
+    ///
+    /// # Example
+    ///
+    /// `hot` => `[[gnu::hot]]` (C++), `__attribute__((hot))` (C)
     pub fn push_attribute(&mut self, attr: &str) -> &mut Self {
-        self.attributes.push(String::from(attr));
+        self.push_attribute_spec(AttributeSpec::new_gnu(attr))
+    }
+
+    /// adds a new attribute to the function
+    pub fn push_attribute_spec(&mut self, attr: AttributeSpec) -> &mut Self {
+        self.attributes.push(attr);
         self
     }
 
@@ -208,6 +246,127 @@ impl Function {
         self.toggle_extern(true)
     }
 
+    /// sets the function to never return
+    ///
+    /// # Example
+    ///
+    /// void foo()   ->  _Noreturn void foo()     (C)
+    /// void foo()   ->  [[noreturn]] void foo()  (C++)
+    pub fn toggle_noreturn(&mut self, val: bool) -> &mut Self {
+        self.is_noreturn = val;
+        self
+    }
+
+    /// makes the function to never return
+    pub fn set_noreturn(&mut self) -> &mut Self {
+        self.toggle_noreturn(true)
+    }
+
+    /// sets whether only the declaration of the function is emitted, even when
+    /// formatting the definition
+    pub fn toggle_decl_only(&mut self, val: bool) -> &mut Self {
+        self.is_decl_only = val;
+        self
+    }
+
+    /// emits only the declaration of the function
+    pub fn set_decl_only(&mut self) -> &mut Self {
+        self.toggle_decl_only(true)
+    }
+
+    /// sets the calling convention of the function
+    ///
+    /// # Example
+    ///
+    /// void foo()   ->  void __stdcall foo()
+    pub fn calling_convention(&mut self, cc: &str) -> &mut Self {
+        self.calling_convention = Some(String::from(cc));
+        self
+    }
+
+    /// sets the `__declspec` of the function, e.g., `dllexport`
+    ///
+    /// The specifier is only emitted for MSVC, see `Dialect::Msvc`. Otherwise, `dllexport`
+    /// becomes `__attribute__((visibility("default")))`.
+    ///
+    /// # Example
+    ///
+    /// void foo()   ->  __declspec(dllexport) void foo()
+    pub fn declspec(&mut self, spec: &str) -> &mut Self {
+        self.declspec = Some(String::from(spec));
+        self
+    }
+
+    /// sets the macro selecting the DLL storage class of the function
+    ///
+    /// # Example
+    ///
+    /// void foo()   ->  MYLIB_API void foo()
+    pub fn export_macro(&mut self, name: &str) -> &mut Self {
+        self.export_macro = Some(String::from(name));
+        self
+    }
+
+    /// sets the function to have C language linkage, emitted in C++ only
+    ///
+    /// # Example
+    ///
+    /// void foo()   ->  extern "C" void foo()
+    pub fn toggle_extern_c(&mut self, val: bool) -> &mut Self {
+        self.is_extern_c = val;
+        self
+    }
+
+    /// gives the function C language linkage
+    pub fn set_extern_c(&mut self) -> &mut Self {
+        self.toggle_extern_c(true)
+    }
+
+    /// creates an `extern "C"` wrapper named `<name>_c` forwarding all parameters to
+    /// this function
+    ///
+    /// # Example
+    ///
+    /// `int foo(int a)` => `extern "C" int foo_c(int a) { return foo(a); }`
+    pub fn c_wrapper(&self) -> Function {
+        let mut f = Function::with_string(format!("{}_c", self.name), self.ret.clone());
+        f.params = self.params.clone();
+        f.set_extern_c();
+
+        let args = self.params.iter().map(|p| p.to_expr()).collect();
+        if matches!(self.ret.basetype(), BaseType::Void) && !self.ret.is_ptr() {
+            f.body.fn_call(&self.name, args);
+        } else {
+            f.body.return_expr(Expr::fn_call(&self.name, args));
+        }
+        f
+    }
+
+    /// replaces the documentation with a skeleton derived from the signature
+    ///
+    /// The skeleton has a `@brief` line, a `@param` line per parameter including the
+    /// parameter's documentation, and a `@return` line unless the function returns void.
+    ///
+    /// # Example
+    ///
+    /// `int foo(int a)` => `/// @brief foo`, `///`, `/// @param a`, `/// @return`
+    pub fn generate_doc_skeleton(&mut self) -> &mut Self {
+        let mut doc = Doc::new();
+        doc.add_raw_line(&format!("@brief {}", self.name));
+        doc.add_raw_line("");
+        for p in &self.params {
+            match p.doc_ref().map(|d| d.to_single_line()) {
+                Some(d) if !d.is_empty() => doc.add_raw_line(&format!("@param {} {d}", p.name())),
+                _ => doc.add_raw_line(&format!("@param {}", p.name())),
+            };
+        }
+        if !matches!(self.ret.basetype(), BaseType::Void) || self.ret.is_ptr() {
+            doc.add_raw_line("@return");
+        }
+        self.doc = Some(doc);
+        self
+    }
+
     /// sets the body for the function
     pub fn set_body(&mut self, body: Block) -> &mut Self {
         if !body.is_empty() {
@@ -222,11 +381,50 @@ impl Function {
         &mut self.body
     }
 
+    /// checks whether the body of the function is empty
+    pub fn body_is_empty(&self) -> bool {
+        self.body.is_empty()
+    }
+
+    /// returns the number of statements in the body of the function
+    ///
+    /// See [`Block::stmt_count`] for what is considered a statement.
+    pub fn stmt_count(&self) -> usize {
+        self.body.stmt_count()
+    }
+
+    /// obtains the types used by the function
+    pub(crate) fn used_types(&self) -> Vec<Type> {
+        let mut ret = vec![self.ret.clone()];
+        ret.extend(self.params.iter().map(|p| p.to_type()));
+        ret.extend(self.body.used_types());
+        ret
+    }
+
     pub fn do_fmt(&self, fmt: &mut Formatter<'_>, decl_only: bool) -> fmt::Result {
+        let decl_only = decl_only || self.is_decl_only;
         if let Some(ref docs) = self.doc {
             docs.fmt(fmt)?;
         }
 
+        if self.is_extern_c && fmt.dialect().is_cpp() {
+            write!(fmt, "extern \"C\" ")?;
+        }
+
+        let dialect = fmt.dialect();
+        for attr in self.attributes.iter().filter(|a| a.is_supported(dialect)) {
+            attr.fmt(fmt)?;
+            write!(fmt, " ")?;
+        }
+
+        if self.is_noreturn {
+            if fmt.dialect().is_cpp() {
+                write!(fmt, "[[noreturn]] ")?;
+            } else {
+                write!(fmt, "_Noreturn ")?;
+            }
+        }
+
         if self.body.is_empty() && self.is_extern {
             write!(fmt, "extern ")?;
         }
@@ -235,6 +433,14 @@ impl Function {
             write!(fmt, "static ")?;
         }
 
+        if let Some(name) = &self.export_macro {
+            write!(fmt, "{name} ")?;
+        }
+
+        if let Some(spec) = &self.declspec {
+            fmt.write_declspec(spec)?;
+        }
+
         if self.is_inline {
             write!(fmt, "inline ")?;
         }
@@ -242,6 +448,10 @@ impl Function {
         // the type
         self.ret.fmt(fmt)?;
 
+        if let Some(cc) = &self.calling_convention {
+            write!(fmt, " {cc}")?;
+        }
+
         write!(fmt, " {}(", self.name)?;
         if self.params.is_empty() {
             write!(fmt, "void")?;
@@ -255,12 +465,8 @@ impl Function {
         }
         write!(fmt, ")")?;
 
-        if !self.attributes.is_empty() {
-            write!(fmt, "__attribute__() // TODO")?;
-        }
-
         // if there is no body, and is inline or we only want the declaration
-        if !self.body.is_empty() && (!decl_only || self.is_inline) {
+        if !self.body.is_empty() && !self.is_decl_only && (!decl_only || self.is_inline) {
             fmt.block(|fmt| self.body.fmt(fmt))?;
             writeln!(fmt)
         } else {
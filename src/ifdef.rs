@@ -63,9 +63,9 @@ impl IfDef {
         &mut self.then
     }
 
-    /// obtains the scope to the other block
+    /// obtains the scope to the other block, creating it if needed
     pub fn other_scope(&mut self) -> &mut Scope {
-        &mut self.then
+        self.other.get_or_insert_with(Scope::new)
     }
 
     pub fn guard(&mut self) -> &mut Self {
@@ -73,7 +73,17 @@ impl IfDef {
         self
     }
 
-    // formats the ifdef block
+    /// obtains the scopes of both branches
+    pub(crate) fn scopes(&self) -> Vec<&Scope> {
+        let mut ret = vec![&self.then];
+        if let Some(o) = &self.other {
+            ret.push(o);
+        }
+        ret
+    }
+
+    // formats the ifdef block, declarations and definitions of the nested scopes are
+    // split the same way as the enclosing scope
     pub fn do_fmt(&self, fmt: &mut Formatter<'_>, only_decls: bool) -> fmt::Result {
         writeln!(fmt, "\n")?;
         if self.is_guard {
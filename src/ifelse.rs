@@ -88,6 +88,16 @@ impl IfElse {
         &mut self.other
     }
 
+    /// obtains the nested blocks of the conditional
+    pub(crate) fn blocks(&self) -> Vec<&Block> {
+        vec![&self.then, &self.other]
+    }
+
+    /// obtains the expressions of the conditional, i.e., the condition
+    pub(crate) fn exprs(&self) -> Vec<&Expr> {
+        vec![&self.cond]
+    }
+
     /// formats the conditional
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         write!(fmt, "if (")?;
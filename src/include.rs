@@ -31,6 +31,19 @@ use std::fmt::{self, Write};
 
 use crate::formatter::Formatter;
 
+/// Defines the group of an include, used to emit includes in sections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IncludeGroup {
+    /// C system headers, e.g., `<stdio.h>`
+    CSystem,
+    /// C++ system headers, e.g., `<vector>`
+    CppSystem,
+    /// project headers, e.g., `"foo.h"`
+    Project,
+    /// generated headers
+    Generated,
+}
+
 /// Defines an struct field
 #[derive(Debug, Clone)]
 pub struct Include {
@@ -40,8 +53,17 @@ pub struct Include {
     /// whether this is a system include
     is_system: bool,
 
+    /// the group of the include, derived from the path if not set
+    group: Option<IncludeGroup>,
+
     /// The documentation comment of the macro
     doc: Option<String>,
+
+    /// whether the include is guarded by `#if __has_include(...)`
+    has_include_guard: bool,
+
+    /// the include used if the guarded header is not available
+    fallback: Option<Box<Include>>,
 }
 
 impl Include {
@@ -55,7 +77,10 @@ impl Include {
         Include {
             path,
             is_system: false,
+            group: None,
             doc: None,
+            has_include_guard: false,
+            fallback: None,
         }
     }
 
@@ -64,7 +89,10 @@ impl Include {
         Include {
             path: String::from(path),
             is_system: true,
+            group: None,
             doc: None,
+            has_include_guard: false,
+            fallback: None,
         }
     }
 
@@ -80,19 +108,91 @@ impl Include {
         self
     }
 
-    /// Formats the variant using the given formatter.
-    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        write!(fmt, "#include ")?;
+    /// obtains the path of the include
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// tests whether this is a system include
+    pub fn is_system(&self) -> bool {
+        self.is_system
+    }
+
+    /// sets the group of the include
+    pub fn set_group(&mut self, group: IncludeGroup) -> &mut Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// guards the include with `#if __has_include(...)`
+    ///
+    /// # Example
+    ///
+    /// `#include <optional>` => `#if __has_include(<optional>)` ... `#endif`
+    pub fn toggle_has_include(&mut self, val: bool) -> &mut Self {
+        self.has_include_guard = val;
+        self
+    }
+
+    /// guards the include with `#if __has_include(...)`
+    pub fn set_has_include(&mut self) -> &mut Self {
+        self.toggle_has_include(true)
+    }
+
+    /// sets the include used in the `#else` branch if the header is not available
+    ///
+    /// This implies guarding the include with `#if __has_include(...)`.
+    pub fn set_fallback(&mut self, fallback: Include) -> &mut Self {
+        self.fallback = Some(Box::new(fallback));
+        self.set_has_include()
+    }
+
+    /// obtains the group of the include
+    ///
+    /// If no group has been set, system includes ending in `.h` are C system
+    /// headers, other system includes are C++ headers, and the rest are project headers.
+    pub fn group(&self) -> IncludeGroup {
+        match self.group {
+            Some(g) => g,
+            None if self.is_system && self.path.ends_with(".h") => IncludeGroup::CSystem,
+            None if self.is_system => IncludeGroup::CppSystem,
+            None => IncludeGroup::Project,
+        }
+    }
+
+    /// formats the header name, `<path>` or `"path"`
+    fn fmt_header(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         if self.is_system {
-            write!(fmt, "<{}>", self.path)?;
+            write!(fmt, "<{}>", self.path)
         } else {
-            write!(fmt, "\"{}\"", self.path)?;
+            write!(fmt, "\"{}\"", self.path)
         }
+    }
+
+    /// Formats the variant using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if self.has_include_guard {
+            write!(fmt, "#if __has_include(")?;
+            self.fmt_header(fmt)?;
+            writeln!(fmt, ")")?;
+        }
+
+        write!(fmt, "#include ")?;
+        self.fmt_header(fmt)?;
 
         if let Some(d) = &self.doc {
-            writeln!(fmt, "  // {d}")
+            writeln!(fmt, "  // {d}")?;
         } else {
-            writeln!(fmt)
+            writeln!(fmt)?;
+        }
+
+        if self.has_include_guard {
+            if let Some(f) = &self.fallback {
+                writeln!(fmt, "#else")?;
+                f.as_ref().fmt(fmt)?;
+            }
+            writeln!(fmt, "#endif")?;
         }
+        Ok(())
     }
 }
@@ -28,10 +28,12 @@
 //! The Cgen Rust library provides a builder API for generating C code.
 
 mod attribute;
+mod attribute_spec;
 mod block;
 mod class;
 mod comment;
 mod constructor;
+mod dialect;
 mod doc;
 mod expr;
 mod field;
@@ -41,10 +43,15 @@ mod ifdef;
 mod ifelse;
 mod include;
 mod loops;
+mod manifest;
 mod method;
+mod namespace;
 mod param;
 mod scope;
+mod static_assert;
 mod switch;
+mod tagged_union;
+mod trycatch;
 mod union;
 mod variable;
 mod variant;
@@ -55,28 +62,36 @@ mod r#struct;
 mod r#type;
 
 pub use attribute::Attribute;
+pub use attribute_spec::AttributeSpec;
 pub use block::Block;
 pub use class::Class;
 pub use comment::Comment;
 pub use constructor::{Constructor, Destructor};
+pub use dialect::Dialect;
 pub use doc::Doc;
 pub use expr::Expr;
 pub use field::Field;
 use formatter::Formatter;
+pub use formatter::LineEnding;
 pub use function::Function;
 pub use ifdef::IfDef;
 pub use ifelse::IfElse;
-pub use include::Include;
-pub use loops::{DoWhileLoop, ForLoop, WhileLoop};
+pub use include::{Include, IncludeGroup};
+pub use loops::{DoWhileLoop, ForLoop, RangeForLoop, WhileLoop};
+pub use manifest::Manifest;
 pub use method::Method;
+pub use namespace::Namespace;
 pub use param::{FunctionParam, MethodParam};
 pub use r#macro::Macro;
 pub use scope::Scope;
+pub use static_assert::StaticAssert;
 pub use switch::Switch;
+pub use tagged_union::TaggedUnion;
+pub use trycatch::TryCatch;
 pub use union::Union;
 pub use variable::Variable;
 pub use variant::Variant;
 
 pub use r#enum::Enum;
 pub use r#struct::Struct;
-pub use r#type::{BaseType, Type, Visibility};
+pub use r#type::{BaseType, TemplateArg, Type, Visibility};
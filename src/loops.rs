@@ -30,7 +30,7 @@
 // std includes
 use std::fmt::{self, Display, Write};
 
-use crate::{Block, Expr, Formatter};
+use crate::{Block, Expr, Formatter, Type};
 
 #[derive(Debug, Clone)]
 pub struct WhileLoop {
@@ -67,6 +67,16 @@ impl WhileLoop {
         &mut self.body
     }
 
+    /// obtains the nested blocks of the loop
+    pub(crate) fn blocks(&self) -> Vec<&Block> {
+        vec![&self.body]
+    }
+
+    /// obtains the expressions of the loop, i.e., the condition
+    pub(crate) fn exprs(&self) -> Vec<&Expr> {
+        vec![&self.cond]
+    }
+
     /// formats the loop
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         write!(fmt, "while (")?;
@@ -122,6 +132,16 @@ impl DoWhileLoop {
         &mut self.body
     }
 
+    /// obtains the nested blocks of the loop
+    pub(crate) fn blocks(&self) -> Vec<&Block> {
+        vec![&self.body]
+    }
+
+    /// obtains the expressions of the loop, i.e., the condition
+    pub(crate) fn exprs(&self) -> Vec<&Expr> {
+        vec![&self.cond]
+    }
+
     /// formats the loop
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         write!(fmt, "do ")?;
@@ -195,6 +215,16 @@ impl ForLoop {
         &mut self.body
     }
 
+    /// obtains the nested blocks of the loop
+    pub(crate) fn blocks(&self) -> Vec<&Block> {
+        vec![&self.body]
+    }
+
+    /// obtains the expressions of the loop, i.e., the initializer, condition, and step
+    pub(crate) fn exprs(&self) -> Vec<&Expr> {
+        [&self.init, &self.cond, &self.step].into_iter().flatten().collect()
+    }
+
     /// formats the loop
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         write!(fmt, "for (")?;
@@ -210,7 +240,7 @@ impl ForLoop {
             step.fmt(fmt)?;
         }
         writeln!(fmt, ") ")?;
-        if self.body.is_empty() {
+        if !self.body.is_empty() {
             fmt.block(|f| self.body.fmt(f))?;
             writeln!(fmt)
         } else {
@@ -232,3 +262,83 @@ impl Display for ForLoop {
         write!(f, "{ret}")
     }
 }
+
+/// a C++ range-based for loop: `for (auto& x : range) { ... }`
+#[derive(Debug, Clone)]
+pub struct RangeForLoop {
+    /// the name of the loop variable
+    decl: String,
+    /// the type of the loop variable, `auto&` if not set
+    ty: Option<Type>,
+    /// the range expression to iterate over
+    range: Expr,
+    /// the body of the loop
+    body: Block,
+}
+
+impl RangeForLoop {
+    /// creates a new range-based for loop over the supplied range
+    pub fn new(decl: &str, range: &Expr) -> Self {
+        Self::with_range(String::from(decl), range.clone())
+    }
+
+    /// creates a new range-based for loop taking ownership of the supplied range
+    pub fn with_range(decl: String, range: Expr) -> Self {
+        RangeForLoop {
+            decl,
+            ty: None,
+            range,
+            body: Block::new(),
+        }
+    }
+
+    /// sets the type of the loop variable
+    pub fn set_type(&mut self, ty: Type) -> &mut Self {
+        self.ty = Some(ty);
+        self
+    }
+
+    /// sets the body block of the loop
+    pub fn set_body(&mut self, body: Block) -> &mut Self {
+        self.body = body;
+        self
+    }
+
+    /// obtains a mutable reference to the body block of the loop
+    pub fn body(&mut self) -> &mut Block {
+        &mut self.body
+    }
+
+    /// obtains the nested blocks of the loop
+    pub(crate) fn blocks(&self) -> Vec<&Block> {
+        vec![&self.body]
+    }
+
+    /// obtains the expressions of the loop, i.e., the range
+    pub(crate) fn exprs(&self) -> Vec<&Expr> {
+        vec![&self.range]
+    }
+
+    /// formats the loop
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "for (")?;
+        if let Some(ty) = &self.ty {
+            ty.fmt(fmt)?;
+            write!(fmt, " {} : ", self.decl)?;
+        } else {
+            write!(fmt, "auto& {} : ", self.decl)?;
+        }
+        self.range.fmt(fmt)?;
+        write!(fmt, ")")?;
+        fmt.block(|f| self.body.fmt(f))?;
+        writeln!(fmt)
+    }
+}
+
+impl Display for RangeForLoop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ret = String::new();
+        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        write!(f, "{ret}")
+    }
+}
@@ -0,0 +1,145 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2022 Reto Achermann
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Symbol Manifest
+//!
+//! This module defines a manifest of the symbols generated by a scope, e.g., to
+//! let a build system know which functions and types a generated file provides.
+
+// std includes
+use std::fmt::{self, Display};
+
+/// the symbols defined by a generated file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    /// the name of the generated file
+    file: Option<String>,
+
+    /// the names of the defined functions
+    functions: Vec<String>,
+
+    /// the names of the defined types
+    types: Vec<String>,
+
+    /// the names of the defined global variables
+    variables: Vec<String>,
+}
+
+impl Manifest {
+    /// creates a new, empty manifest for the given file
+    pub fn new(file: Option<&str>) -> Self {
+        Self {
+            file: file.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    /// obtains the name of the generated file
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// obtains the names of the defined functions
+    pub fn functions(&self) -> &[String] {
+        &self.functions
+    }
+
+    /// obtains the names of the defined types
+    pub fn types(&self) -> &[String] {
+        &self.types
+    }
+
+    /// obtains the names of the defined global variables
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+
+    /// adds a function to the manifest
+    pub fn push_function(&mut self, name: &str) -> &mut Self {
+        self.functions.push(String::from(name));
+        self
+    }
+
+    /// adds a type to the manifest
+    pub fn push_type(&mut self, name: &str) -> &mut Self {
+        self.types.push(String::from(name));
+        self
+    }
+
+    /// adds a global variable to the manifest
+    pub fn push_variable(&mut self, name: &str) -> &mut Self {
+        self.variables.push(String::from(name));
+        self
+    }
+
+    /// serializes the manifest as JSON
+    ///
+    /// # Example
+    ///
+    /// `{"file": "foo.c", "functions": ["foo"], "types": [], "variables": []}`
+    pub fn to_json(&self) -> String {
+        let mut ret = String::from("{\n");
+        match &self.file {
+            Some(f) => ret.push_str(&format!("  \"file\": {},\n", json_str(f))),
+            None => ret.push_str("  \"file\": null,\n"),
+        }
+        let lists = [
+            ("functions", &self.functions),
+            ("types", &self.types),
+            ("variables", &self.variables),
+        ];
+        for (i, (key, names)) in lists.iter().enumerate() {
+            let names: Vec<String> = names.iter().map(|n| json_str(n)).collect();
+            ret.push_str(&format!("  \"{key}\": [{}]", names.join(", ")));
+            ret.push_str(if i + 1 < lists.len() { ",\n" } else { "\n" });
+        }
+        ret.push('}');
+        ret
+    }
+}
+
+/// quotes and escapes the string as a JSON string literal
+fn json_str(s: &str) -> String {
+    let mut ret = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if (c as u32) < 0x20 => ret.push_str(&format!("\\u{:04x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+impl Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
+}
@@ -30,7 +30,11 @@
 
 use std::fmt::{self, Write};
 
-use crate::{Block, Doc, Formatter, MethodParam, Type, Visibility};
+use crate::expr::escape_str;
+use crate::{
+    AttributeSpec, Block, Doc, Formatter, Function, FunctionParam, MethodParam, TemplateArg, Type,
+    Visibility,
+};
 
 /// holds a method definition
 #[derive(Debug, Clone)]
@@ -68,9 +72,36 @@ pub struct Method {
     /// sets the method to be const
     is_const: bool,
 
+    /// whether the method never returns
+    is_noreturn: bool,
+
+    /// whether this is a conversion operator, `operator T()`
+    is_conversion: bool,
+
+    /// the calling convention of the method, e.g., `__stdcall`
+    calling_convention: Option<String>,
+
+    /// the `__declspec` of the method, e.g., `dllexport`
+    declspec: Option<String>,
+
+    /// attributes of the method
+    attributes: Vec<AttributeSpec>,
+
+    /// whether the method is explicitly defaulted, `= default`
+    is_default: bool,
+
+    /// whether the method is deleted, `= delete`
+    is_delete: bool,
+
+    /// the reason for deleting the method, `= delete("reason")`
+    delete_reason: Option<String>,
+
     /// wheter the definition is inside of the class
     is_inside: bool,
 
+    /// the class template arguments of an explicit specialization of the method
+    specialization: Option<Vec<TemplateArg>>,
+
     /// the body of the method, a sequence of statements
     body: Block,
 }
@@ -94,16 +125,71 @@ impl Method {
             is_pure: false,
             is_override: false,
             is_const: false,
+            is_noreturn: false,
+            is_conversion: false,
+            calling_convention: None,
+            declspec: None,
+            attributes: Vec::new(),
+            is_default: false,
+            is_delete: false,
+            delete_reason: None,
             is_inside: false,
+            specialization: None,
             body: Block::new(),
         }
     }
 
+    /// creates a new const conversion operator to the given type
+    ///
+    /// # Example
+    ///
+    /// `bool` => `operator bool() const`
+    pub fn new_conversion(ty: Type) -> Self {
+        let mut m = Self::with_string(format!("operator {ty}"), ty);
+        m.is_conversion = true;
+        m.is_const = true;
+        m
+    }
+
     /// returns the name of the method
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// checks whether the method has the given name and parameter types
+    pub(crate) fn has_signature(&self, name: &str, params: &[Type]) -> bool {
+        self.name == name
+            && self.params.len() == params.len()
+            && self.params.iter().zip(params).all(|(p, t)| p.to_type().is_same(t))
+    }
+
+    /// lowers the method to a C function taking the object as a `self` pointer
+    ///
+    /// The `self` pointer points to const if the method is const, static methods
+    /// take no `self` pointer. The body is copied as is, so it must not use `this`.
+    ///
+    /// # Example
+    ///
+    /// `int get(void) const` => `int get(const struct Foo * self)`
+    pub fn to_c_function(&self, class_ty: &Type) -> Function {
+        let mut f = Function::new(&self.name, self.ret.clone());
+        if let Some(d) = &self.doc {
+            f.doc(d.clone());
+        }
+        if !self.is_static {
+            let mut this = class_ty.to_ptr();
+            if self.is_const {
+                this.const_pointee();
+            }
+            f.push_param(FunctionParam::new("self", this));
+        }
+        for p in &self.params {
+            f.push_param(FunctionParam::new(p.name(), p.to_type()));
+        }
+        f.set_body(self.body.clone());
+        f
+    }
+
     /// obtains the type for this function
     pub fn to_type(&self) -> Type {
         panic!("needs to implement a corresponding type.")
@@ -223,6 +309,109 @@ impl Method {
         self.toggle_const(true)
     }
 
+    /// sets the method to never return
+    ///
+    /// # Example
+    ///
+    /// void foo()   -> [[noreturn]] void foo()
+    pub fn toggle_noreturn(&mut self, val: bool) -> &mut Self {
+        self.is_noreturn = val;
+        self
+    }
+
+    /// makes the method to never return
+    pub fn set_noreturn(&mut self) -> &mut Self {
+        self.toggle_noreturn(true)
+    }
+
+    /// adds a new GNU attribute to the method
+    ///
+    /// # Example
+    ///
+    /// `hot` => `[[gnu::hot]]` (C++), `__attribute__((hot))` (C)
+    pub fn push_attribute(&mut self, attr: &str) -> &mut Self {
+        self.push_attribute_spec(AttributeSpec::new_gnu(attr))
+    }
+
+    /// adds a new attribute to the method
+    pub fn push_attribute_spec(&mut self, attr: AttributeSpec) -> &mut Self {
+        self.attributes.push(attr);
+        self
+    }
+
+    /// sets the method to be explicitly defaulted
+    ///
+    /// # Example
+    ///
+    /// Foo & operator=(Foo const & other)  -> Foo & operator=(Foo const & other) = default;
+    pub fn toggle_default(&mut self, val: bool) -> &mut Self {
+        if val {
+            self.body.clear();
+            self.is_delete = false;
+        }
+        self.is_default = val;
+        self
+    }
+
+    /// makes the method explicitly defaulted
+    pub fn set_default(&mut self) -> &mut Self {
+        self.toggle_default(true)
+    }
+
+    /// sets the method to be deleted
+    ///
+    /// # Example
+    ///
+    /// Foo & operator=(Foo const & other)  -> Foo & operator=(Foo const & other) = delete;
+    pub fn toggle_delete(&mut self, val: bool) -> &mut Self {
+        if val {
+            self.body.clear();
+            self.is_default = false;
+        }
+        self.is_delete = val;
+        self
+    }
+
+    /// makes the method deleted
+    pub fn set_delete(&mut self) -> &mut Self {
+        self.toggle_delete(true)
+    }
+
+    /// deletes the method giving the reason for the deletion
+    ///
+    /// The reason requires C++26 and is only emitted if enabled with `Scope::set_delete_reasons`.
+    ///
+    /// # Example
+    ///
+    /// void foo()  -> void foo() = delete("use bar");
+    pub fn set_delete_reason(&mut self, reason: &str) -> &mut Self {
+        self.delete_reason = Some(String::from(reason));
+        self.set_delete()
+    }
+
+    /// sets the calling convention of the method
+    ///
+    /// # Example
+    ///
+    /// void foo()   ->  void __stdcall foo()
+    pub fn calling_convention(&mut self, cc: &str) -> &mut Self {
+        self.calling_convention = Some(String::from(cc));
+        self
+    }
+
+    /// sets the `__declspec` of the method, e.g., `dllexport`
+    ///
+    /// The specifier is only emitted for MSVC, see `Dialect::Msvc`. Otherwise, it becomes
+    /// a GNU attribute, e.g., `__attribute__((noinline))`.
+    ///
+    /// # Example
+    ///
+    /// void foo()   ->  __declspec(noinline) void foo()
+    pub fn declspec(&mut self, spec: &str) -> &mut Self {
+        self.declspec = Some(String::from(spec));
+        self
+    }
+
     /// sets the method to be virtual
     ///
     /// # Example
@@ -296,6 +485,19 @@ impl Method {
         self
     }
 
+    /// explicitly specializes the method for the given class template arguments
+    ///
+    /// The out-of-line definition is prefixed with `template <>` and qualified with
+    /// the specialized class name.
+    ///
+    /// # Example
+    ///
+    /// `[int]` => `template <> void Foo<int>::bar(void) { ... }`
+    pub fn set_specialization(&mut self, args: Vec<TemplateArg>) -> &mut Self {
+        self.specialization = Some(args);
+        self
+    }
+
     /// this method is defined inside
     pub fn set_inside_def(&mut self) -> &mut Self {
         self.toggle_inside_def(true)
@@ -315,8 +517,42 @@ impl Method {
         &mut self.body
     }
 
+    /// checks whether the body of the method is empty
+    pub fn body_is_empty(&self) -> bool {
+        self.body.is_empty()
+    }
+
+    /// returns the number of statements in the body of the method
+    ///
+    /// See [`Block::stmt_count`] for what is considered a statement.
+    pub fn stmt_count(&self) -> usize {
+        self.body.stmt_count()
+    }
+
+    /// obtains the types used by the method
+    pub(crate) fn used_types(&self) -> Vec<Type> {
+        let mut ret = vec![self.ret.clone()];
+        ret.extend(self.params.iter().map(|p| p.to_type()));
+        ret.extend(self.body.used_types());
+        ret
+    }
+
+    /// renames the class in the return and parameter types, e.g., of the assignment
+    /// operators
+    pub(crate) fn rename_class(&mut self, from: &str, to: &str) {
+        self.ret.rename_class(from, to);
+        for p in &mut self.params {
+            p.rename_class(from, to);
+        }
+    }
+
     /// Formats the attribute using the given formatter.
     pub fn do_fmt(&self, fmt: &mut Formatter<'_>, decl_only: bool) -> fmt::Result {
+        // defaulted or deleted methods are defined in the declaration
+        if !decl_only && (self.is_default || self.is_delete) {
+            return Ok(());
+        }
+
         if !self.body.is_empty() | self.doc.is_some() {
             writeln!(fmt)?;
         }
@@ -325,10 +561,31 @@ impl Method {
             docs.fmt(fmt)?;
         }
 
+        if decl_only {
+            let dialect = fmt.dialect();
+            for attr in self.attributes.iter().filter(|a| a.is_supported(dialect)) {
+                attr.fmt(fmt)?;
+                write!(fmt, " ")?;
+            }
+        }
+
+        if self.is_noreturn && decl_only {
+            write!(fmt, "[[noreturn]] ")?;
+        }
+
         if self.is_static && decl_only {
             write!(fmt, "static ")?;
         }
 
+        if let Some(spec) = self.declspec.as_ref().filter(|_| decl_only) {
+            fmt.write_declspec(spec)?;
+        }
+
+        let spec = self.specialization.as_deref().filter(|_| !decl_only);
+        if spec.is_some() {
+            writeln!(fmt, "template <>")?;
+        }
+
         if self.is_inline {
             write!(fmt, "inline ")?;
         }
@@ -337,13 +594,29 @@ impl Method {
             write!(fmt, "virtual ")?;
         }
 
-        self.ret.fmt(fmt)?;
-        if decl_only {
-            write!(fmt, " {}", self.name)?;
+        // conversion operators have no return type in front of their name
+        if self.is_conversion {
+            if decl_only {
+                write!(fmt, "{}", self.name)?;
+            } else {
+                fmt.write_scoped_specialized(self.name.as_str(), spec.unwrap_or_default())?;
+            }
         } else {
-            fmt.write_scoped_name(self.name.as_str())?;
+            self.ret.fmt(fmt)?;
+            if let Some(cc) = &self.calling_convention {
+                write!(fmt, " {cc}")?;
+            }
+            if decl_only {
+                write!(fmt, " {}", self.name)?;
+            } else {
+                write!(fmt, " ")?;
+                fmt.write_scoped_specialized(self.name.as_str(), spec.unwrap_or_default())?;
+            }
         }
-        if self.params.is_empty() {
+
+        if self.params.is_empty() && self.is_conversion {
+            write!(fmt, "()")?;
+        } else if self.params.is_empty() {
             write!(fmt, "(void)")?;
         } else {
             write!(fmt, "(")?;
@@ -356,7 +629,8 @@ impl Method {
             write!(fmt, ")")?;
         }
 
-        if self.is_const && decl_only {
+        // the definition keeps `const`, but drops `virtual` and `override`
+        if self.is_const {
             write!(fmt, " const")?;
         }
 
@@ -368,6 +642,19 @@ impl Method {
             return write!(fmt, " = 0;");
         }
 
+        if self.body.is_empty() && self.is_default {
+            return writeln!(fmt, " = default;");
+        }
+
+        if self.body.is_empty() && self.is_delete {
+            return match &self.delete_reason {
+                Some(r) if fmt.delete_reasons() => {
+                    writeln!(fmt, " = delete(\"{}\");", escape_str(r))
+                }
+                _ => writeln!(fmt, " = delete;"),
+            };
+        }
+
         // if we want to have the declaration only, then do that,
         // but only if it's not a inside method or an inline method
         if self.body.is_empty() || (decl_only && !(self.is_inside || self.is_inline)) {
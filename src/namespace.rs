@@ -0,0 +1,101 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2022 Reto Achermann
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Namespace
+//!
+//! This module provides C++ namespaces wrapping a nested scope.
+
+use std::fmt::{self, Write};
+
+use crate::{Formatter, Scope};
+
+/// defines a C++ namespace
+#[derive(Debug, Clone)]
+pub struct Namespace {
+    /// the name of the namespace
+    name: String,
+
+    /// whether this is an inline namespace
+    is_inline: bool,
+
+    /// the items inside the namespace
+    scope: Scope,
+}
+
+impl Namespace {
+    /// creates a new namespace with the given name
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: String::from(name),
+            is_inline: false,
+            scope: Scope::new(),
+        }
+    }
+
+    /// obtains the name of the namespace
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// obtains the scope of the namespace
+    pub fn scope(&mut self) -> &mut Scope {
+        &mut self.scope
+    }
+
+    /// obtains the nested scope of the namespace
+    pub(crate) fn scopes(&self) -> Vec<&Scope> {
+        vec![&self.scope]
+    }
+
+    /// sets the namespace to be inline
+    ///
+    /// # Example
+    ///
+    /// `namespace v1 {` => `inline namespace v1 {`
+    pub fn toggle_inline(&mut self, val: bool) -> &mut Self {
+        self.is_inline = val;
+        self
+    }
+
+    /// makes the namespace inline
+    pub fn set_inline(&mut self) -> &mut Self {
+        self.toggle_inline(true)
+    }
+
+    /// formats the namespace, declarations and definitions of the nested scope are
+    /// split the same way as the enclosing scope
+    pub fn do_fmt(&self, fmt: &mut Formatter<'_>, only_decls: bool) -> fmt::Result {
+        if self.is_inline {
+            write!(fmt, "inline ")?;
+        }
+        writeln!(fmt, "namespace {} {{", self.name)?;
+        self.scope.do_fmt(fmt, only_decls)?;
+        writeln!(fmt, "\n}} // namespace {}", self.name)
+    }
+
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.do_fmt(fmt, false)
+    }
+}
@@ -43,17 +43,25 @@ pub struct FunctionParam {
 
     /// The documentation comment of the parameter
     doc: Option<Doc>,
+
+    /// whether the parameter may be unused
+    is_unused: bool,
 }
 
 impl FunctionParam {
-    /// Creates a new `Param`
+    /// Creates a new `Param`, an empty name creates an unnamed parameter
     pub fn new(name: &str, ty: Type) -> Self {
         Self::with_string(String::from(name), ty)
     }
 
     /// Creates a new FunctionParam with the given anme
     pub fn with_string(name: String, ty: Type) -> Self {
-        FunctionParam { name, ty, doc: None }
+        FunctionParam {
+            name,
+            ty,
+            doc: None,
+            is_unused: false,
+        }
     }
 
     /// returns the name of the parameter
@@ -95,10 +103,29 @@ impl FunctionParam {
         self
     }
 
+    /// obtains the documentation comment of the parameter
+    pub(crate) fn doc_ref(&self) -> Option<&Doc> {
+        self.doc.as_ref()
+    }
+
+    /// marks the parameter as possibly unused, silencing compiler warnings
+    ///
+    /// # Example
+    ///
+    /// `int x` => `[[maybe_unused]] int x` (C++), `int x __attribute__((unused))` (C)
+    pub fn toggle_unused(&mut self, val: bool) -> &mut Self {
+        self.is_unused = val;
+        self
+    }
+
+    /// marks the parameter as possibly unused
+    pub fn set_unused(&mut self) -> &mut Self {
+        self.toggle_unused(true)
+    }
+
     /// Formats the variant using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        self.ty.fmt(fmt)?;
-        write!(fmt, " {}", self.name)
+        fmt_param_decl(fmt, &self.ty, &self.name, self.is_unused)
     }
 }
 
@@ -124,16 +151,20 @@ pub struct MethodParam {
 
     /// The documentation comment of the variant
     doc: Option<Doc>,
+
+    /// whether the parameter may be unused
+    is_unused: bool,
 }
 
 impl MethodParam {
-    /// Creates a new `Param`
+    /// Creates a new `Param`, an empty name creates an unnamed parameter
     pub fn new(name: &str, ty: Type) -> Self {
         MethodParam {
             name: String::from(name),
             default: None,
             ty,
             doc: None,
+            is_unused: false,
         }
     }
 
@@ -152,6 +183,11 @@ impl MethodParam {
         self.ty.clone()
     }
 
+    /// renames the class of the parameter type, if it refers to the class `from`
+    pub(crate) fn rename_class(&mut self, from: &str, to: &str) {
+        self.ty.rename_class(from, to);
+    }
+
     /// creates an expression from the variable
     pub fn to_expr(&self) -> Expr {
         Expr::Variable {
@@ -176,6 +212,21 @@ impl MethodParam {
         self
     }
 
+    /// marks the parameter as possibly unused, silencing compiler warnings
+    ///
+    /// # Example
+    ///
+    /// `int x` => `[[maybe_unused]] int x` (C++), `int x __attribute__((unused))` (C)
+    pub fn toggle_unused(&mut self, val: bool) -> &mut Self {
+        self.is_unused = val;
+        self
+    }
+
+    /// marks the parameter as possibly unused
+    pub fn set_unused(&mut self) -> &mut Self {
+        self.toggle_unused(true)
+    }
+
     /// sets the default value of the method parametere
     pub fn set_default_value(&mut self, val: &str) -> &mut Self {
         self.default = Some(String::from(val));
@@ -183,8 +234,7 @@ impl MethodParam {
     }
 
     pub fn do_fmt(&self, fmt: &mut Formatter<'_>, decl_only: bool) -> fmt::Result {
-        self.ty.fmt(fmt)?;
-        write!(fmt, " {}", self.name)?;
+        fmt_param_decl(fmt, &self.ty, &self.name, self.is_unused)?;
         if let Some(s) = &self.default {
             if decl_only {
                 write!(fmt, " = {s}")?;
@@ -206,3 +256,15 @@ impl Display for MethodParam {
         write!(f, "{ret}")
     }
 }
+
+/// formats the declaration of a parameter with the unused attribute of the dialect
+fn fmt_param_decl(fmt: &mut Formatter<'_>, ty: &Type, name: &str, unused: bool) -> fmt::Result {
+    if unused && fmt.dialect().is_cpp() {
+        write!(fmt, "[[maybe_unused]] ")?;
+    }
+    ty.fmt_with_name(fmt, name)?;
+    if unused && !fmt.dialect().is_cpp() {
+        write!(fmt, " __attribute__((unused))")?;
+    }
+    Ok(())
+}
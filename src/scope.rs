@@ -32,8 +32,10 @@ use std::fmt::{self, Write};
 use std::fs;
 use std::path::Path;
 
+use crate::formatter::DEFAULT_LINE_WIDTH;
 use crate::{
-    Class, Comment, Doc, Enum, Formatter, Function, IfDef, Include, Macro, Struct, Type, Union,
+    BaseType, Class, Comment, Dialect, Doc, Enum, Expr, Formatter, Function, IfDef, Include,
+    LineEnding, Macro, Manifest, Namespace, StaticAssert, Struct, TaggedUnion, Type, Union,
     Variable,
 };
 
@@ -43,20 +45,34 @@ pub enum Item {
     Comment(Comment),
     Enum(Enum),
     IfDef(IfDef),
+    Namespace(Namespace),
     Include(Include),
     Macro(Macro),
     Struct(Struct),
     Union(Union),
+    TaggedUnion(TaggedUnion),
+    StaticAssert(StaticAssert),
     Function(Function),
     Class(Class),
     Variable(Variable),
     TypeDef(Type, String),
+    RegionBegin(String),
+    RegionEnd,
     NewLine,
 }
 
 /// defines the scope of the generated C code
+///
+/// Items are emitted in insertion order and only ordered containers are used, so
+/// identical builder calls always produce byte-identical output.
 #[derive(Debug, Clone)]
 pub struct Scope {
+    /// the banner comment emitted as the very first thing, e.g., the license
+    banner: Option<Comment>,
+
+    /// the tool that generated this scope, emitted as a "DO NOT EDIT" marker
+    generated_by: Option<String>,
+
     /// the header document comment
     doc: Option<Doc>,
 
@@ -65,17 +81,71 @@ pub struct Scope {
 
     /// the output file
     file: Option<String>,
+
+    /// whether the includes are emitted in sorted groups
+    group_includes: bool,
+
+    /// the language dialect of the generated code
+    dialect: Dialect,
+
+    /// whether all function prototypes are emitted before the definitions
+    prototypes_first: bool,
+
+    /// verbatim lines emitted before all other items
+    preamble: Vec<String>,
+
+    /// the line ending of the generated code
+    line_ending: LineEnding,
+
+    /// whether preprocessor directives are emitted at column 0
+    left_align_directives: bool,
+
+    /// whether deleted functions carry their reason (C++26)
+    delete_reasons: bool,
+
+    /// whether GNU extensions may be used in the generated code
+    gnu_extensions: bool,
+
+    /// the column limit of the generated code
+    line_width: usize,
 }
 
 impl Scope {
     pub fn new() -> Self {
         Scope {
+            banner: None,
+            generated_by: None,
             doc: None,
             items: Vec::new(),
             file: None,
+            group_includes: false,
+            dialect: Dialect::default(),
+            prototypes_first: false,
+            preamble: Vec::new(),
+            line_ending: LineEnding::default(),
+            left_align_directives: false,
+            delete_reasons: false,
+            gnu_extensions: false,
+            line_width: DEFAULT_LINE_WIDTH,
         }
     }
 
+    /// sets the banner comment emitted before all other output, e.g., the license
+    pub fn set_header_banner(&mut self, banner: Comment) -> &mut Self {
+        self.banner = Some(banner);
+        self
+    }
+
+    /// marks the scope as generated by the given tool
+    ///
+    /// # Example
+    ///
+    /// `mytool` => `// AUTO-GENERATED by mytool — DO NOT EDIT`
+    pub fn generated_by(&mut self, tool: &str) -> &mut Self {
+        self.generated_by = Some(String::from(tool));
+        self
+    }
+
     /// adds a string to the documentation comment to the variant
     pub fn push_doc_str(&mut self, doc: &str) -> &mut Self {
         if let Some(d) = &mut self.doc {
@@ -86,12 +156,38 @@ impl Scope {
         self
     }
 
+    /// adds a verbatim line to the preamble emitted before all other items
+    ///
+    /// # Example
+    ///
+    /// `#define _GNU_SOURCE` before any `#include`
+    pub fn push_preamble(&mut self, line: &str) -> &mut Self {
+        self.preamble.push(String::from(line));
+        self
+    }
+
     /// adds a empty line in the scope
     pub fn push_empty_line(&mut self) -> &mut Self {
         self.items.push(Item::NewLine);
         self
     }
 
+    /// begins a named region for editor folding
+    ///
+    /// # Example
+    ///
+    /// `helpers` => `#pragma region helpers`
+    pub fn begin_region(&mut self, name: &str) -> &mut Self {
+        self.items.push(Item::RegionBegin(String::from(name)));
+        self
+    }
+
+    /// ends the most recently begun region, `#pragma endregion`
+    pub fn end_region(&mut self) -> &mut Self {
+        self.items.push(Item::RegionEnd);
+        self
+    }
+
     /// adds a documetnation comment to the variant
     pub fn doc(&mut self, doc: Doc) -> &mut Self {
         self.doc = Some(doc);
@@ -104,6 +200,98 @@ impl Scope {
         self
     }
 
+    /// sets the language dialect used when formatting the scope to a string or file
+    pub fn set_dialect(&mut self, dialect: Dialect) -> &mut Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// sets the line ending of the generated code, e.g., `\r\n` for Windows
+    pub fn set_line_ending(&mut self, line_ending: LineEnding) -> &mut Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// sets the column limit of the generated code, e.g., the width of heading comments
+    ///
+    /// # Example
+    ///
+    /// `80` => headings are 80 columns wide
+    pub fn set_line_width(&mut self, width: usize) -> &mut Self {
+        self.line_width = width;
+        self
+    }
+
+    /// sets whether preprocessor directives are emitted at column 0
+    ///
+    /// By default, directives inside indented code, e.g., a function body, are
+    /// indented like the surrounding statements. Raw lines are left-aligned if they
+    /// start with a directive such as `#pragma`, other lines starting with `#` keep
+    /// their indentation.
+    pub fn toggle_left_align_directives(&mut self, val: bool) -> &mut Self {
+        self.left_align_directives = val;
+        self
+    }
+
+    /// emits all preprocessor directives at column 0
+    pub fn set_left_align_directives(&mut self) -> &mut Self {
+        self.toggle_left_align_directives(true)
+    }
+
+    /// sets whether deleted functions carry their reason, `= delete("reason")`
+    ///
+    /// Reasons require C++26, by default deleted functions are emitted as `= delete`.
+    pub fn toggle_delete_reasons(&mut self, val: bool) -> &mut Self {
+        self.delete_reasons = val;
+        self
+    }
+
+    /// emits the reasons of deleted functions, `= delete("reason")` (C++26)
+    pub fn set_delete_reasons(&mut self) -> &mut Self {
+        self.toggle_delete_reasons(true)
+    }
+
+    /// sets whether GNU extensions may be used in the generated code
+    ///
+    /// Some expressions are only available in C with GNU extensions, e.g., `Expr::bit_cast`
+    /// uses a statement expression.
+    pub fn toggle_gnu_extensions(&mut self, val: bool) -> &mut Self {
+        self.gnu_extensions = val;
+        self
+    }
+
+    /// allows the use of GNU extensions in the generated code
+    pub fn set_gnu_extensions(&mut self) -> &mut Self {
+        self.toggle_gnu_extensions(true)
+    }
+
+    /// sets whether the prototypes of all functions are emitted before their definitions
+    ///
+    /// The prototypes are emitted at the position of the first function in the scope.
+    pub fn toggle_prototypes_first(&mut self, val: bool) -> &mut Self {
+        self.prototypes_first = val;
+        self
+    }
+
+    /// emits the prototypes of all functions before their definitions
+    pub fn set_prototypes_first(&mut self) -> &mut Self {
+        self.toggle_prototypes_first(true)
+    }
+
+    /// sets whether the includes are emitted first, deduplicated and sorted in groups
+    ///
+    /// The groups are C system, C++ system, project and generated headers,
+    /// separated by an empty line.
+    pub fn toggle_group_includes(&mut self, val: bool) -> &mut Self {
+        self.group_includes = val;
+        self
+    }
+
+    /// emits the includes in sorted groups
+    pub fn set_group_includes(&mut self) -> &mut Self {
+        self.toggle_group_includes(true)
+    }
+
     /// adds a new comment to the scope
     pub fn new_comment(&mut self, comment: &str) -> &mut Comment {
         self.push_comment(Comment::with_str(comment));
@@ -140,6 +328,73 @@ impl Scope {
         self
     }
 
+    /// adds the standard includes required by the types used in the scope
+    ///
+    /// Includes that are already part of the scope are not added again. The new
+    /// includes are placed after the last include of the scope, or at the start.
+    ///
+    /// # Example
+    ///
+    /// `std::string name;`  => `#include <string>`
+    pub fn auto_includes(&mut self) -> &mut Self {
+        let mut incs: Vec<&'static str> = Vec::new();
+        for ty in self.used_types() {
+            let inc = match ty.basetype().required_include() {
+                Some("stdbool.h") if self.dialect.is_cpp() => continue,
+                Some(inc) => inc,
+                None => continue,
+            };
+            let present = self.items.iter().any(|i| match i {
+                Item::Include(v) => v.is_system() && v.path() == inc,
+                _ => false,
+            });
+            if !present && !incs.contains(&inc) {
+                incs.push(inc);
+            }
+        }
+
+        let pos = self
+            .items
+            .iter()
+            .rposition(|i| matches!(i, Item::Include(_)))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let new = incs.into_iter().map(|i| Item::Include(Include::new_system(i)));
+        self.items.splice(pos..pos, new);
+        self
+    }
+
+    /// returns the types that are only used through pointers or references
+    ///
+    /// These types can be forward-declared, e.g., `struct foo;`, instead of including
+    /// the header that defines them. Types defined in this scope are not reported.
+    ///
+    /// # Example
+    ///
+    /// `void f(struct foo * p);` => `struct foo`
+    pub fn minimize_includes(&self) -> Vec<Type> {
+        let defined = self.symbol_manifest();
+        let mut by_value: Vec<Type> = Vec::new();
+        let mut indirect: Vec<Type> = Vec::new();
+        for ty in self.used_types() {
+            let name = match ty.basetype() {
+                BaseType::Struct(n) | BaseType::Union(n) | BaseType::Class(n) => n,
+                _ => continue,
+            };
+            if defined.types().iter().any(|t| t == name) {
+                continue;
+            }
+
+            let base = Type::new(ty.basetype().clone());
+            let uses = if ty.is_indirect() { &mut indirect } else { &mut by_value };
+            if !uses.iter().any(|t| t.is_same(&base)) {
+                uses.push(base);
+            }
+        }
+        indirect.retain(|t| !by_value.iter().any(|v| v.is_same(t)));
+        indirect
+    }
+
     /// adds a new enum to the scope
     pub fn new_enum(&mut self, name: &str) -> &mut Enum {
         self.push_enum(Enum::new(name));
@@ -204,6 +459,38 @@ impl Scope {
         self
     }
 
+    /// adds a new tagged union to the scope
+    pub fn new_tagged_union(&mut self, name: &str) -> &mut TaggedUnion {
+        self.push_tagged_union(TaggedUnion::new(name));
+
+        match *self.items.last_mut().unwrap() {
+            Item::TaggedUnion(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// pushes a tagged union to the scope
+    pub fn push_tagged_union(&mut self, c: TaggedUnion) -> &mut Self {
+        self.items.push(Item::TaggedUnion(c));
+        self
+    }
+
+    /// adds a new static assertion to the scope
+    pub fn new_static_assert(&mut self, cond: Expr, msg: &str) -> &mut StaticAssert {
+        self.push_static_assert(StaticAssert::new(cond, msg));
+
+        match *self.items.last_mut().unwrap() {
+            Item::StaticAssert(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// pushes a static assertion to the scope
+    pub fn push_static_assert(&mut self, c: StaticAssert) -> &mut Self {
+        self.items.push(Item::StaticAssert(c));
+        self
+    }
+
     /// adds a new union to the scope
     pub fn new_function(&mut self, name: &str, ty: Type) -> &mut Function {
         self.push_function(Function::new(name, ty));
@@ -214,6 +501,26 @@ impl Scope {
         }
     }
 
+    /// adds a new function declaration to the scope, its body is never emitted
+    pub fn new_function_decl(&mut self, name: &str, ty: Type) -> &mut Function {
+        let f = self.new_function(name, ty);
+        f.set_decl_only();
+        f
+    }
+
+    /// adds a new `main` function with the standard parameters to the scope
+    ///
+    /// # Example
+    ///
+    /// `int main(int argc, char * * argv)`
+    pub fn new_main(&mut self) -> &mut Function {
+        let int = Type::new_plain_int();
+        let f = self.new_function("main", int.clone());
+        f.new_param("argc", int);
+        f.new_param("argv", Type::new_char().to_ptr().to_ptr());
+        f
+    }
+
     /// pushes a class to the scope
     pub fn push_function(&mut self, c: Function) -> &mut Self {
         self.items.push(Item::Function(c));
@@ -252,6 +559,15 @@ impl Scope {
         self
     }
 
+    /// adds a new global array of `size` elements of the given type to the scope
+    ///
+    /// # Example
+    ///
+    /// `uint8_t`, `16` => `uint8_t name[16];`
+    pub fn new_global_array(&mut self, name: &str, elem_ty: Type, size: usize) -> &mut Variable {
+        self.new_variable(name, elem_ty.to_array(size))
+    }
+
     /// adds a new variable to the scope
     pub fn new_ifdef(&mut self, sym: &str) -> &mut IfDef {
         self.push_ifdef(IfDef::new(sym));
@@ -268,6 +584,38 @@ impl Scope {
         self
     }
 
+    /// adds the macro selecting the DLL storage class of exported symbols
+    ///
+    /// The macro expands to `__declspec(dllexport)` if `build_sym` is defined, and to
+    /// `__declspec(dllimport)` otherwise. Set it as the `export_macro` of the exported
+    /// items.
+    ///
+    /// # Example
+    ///
+    /// `MYLIB_API`, `MYLIB_BUILD` => `#define MYLIB_API __declspec(dllexport)`, ...
+    pub fn new_export_macro(&mut self, name: &str, build_sym: &str) -> &mut IfDef {
+        let ifdef = self.new_ifdef(build_sym);
+        ifdef.then_scope().new_macro(name).set_value("__declspec(dllexport)");
+        ifdef.other_scope().new_macro(name).set_value("__declspec(dllimport)");
+        ifdef
+    }
+
+    /// adds a new namespace to the scope
+    pub fn new_namespace(&mut self, name: &str) -> &mut Namespace {
+        self.push_namespace(Namespace::new(name));
+
+        match *self.items.last_mut().unwrap() {
+            Item::Namespace(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// pushes a namespace to the scope
+    pub fn push_namespace(&mut self, ns: Namespace) -> &mut Self {
+        self.items.push(Item::Namespace(ns));
+        self
+    }
+
     /// adds a new typedef to the scope
     pub fn new_typedef(&mut self, name: &str, ty: Type) -> &mut Self {
         self.items.push(Item::TypeDef(ty, String::from(name)));
@@ -276,10 +624,46 @@ impl Scope {
 
     pub fn do_fmt(&self, fmt: &mut Formatter<'_>, only_decls: bool) -> fmt::Result {
         // documentation and license information
-        self.doc.as_ref().map(|d| d.fmt(fmt));
+        if let Some(b) = &self.banner {
+            b.fmt(fmt)?;
+        }
+        if let Some(tool) = &self.generated_by {
+            writeln!(fmt, "// AUTO-GENERATED by {tool} — DO NOT EDIT")?;
+        }
+        if let Some(d) = &self.doc {
+            d.fmt(fmt)?;
+        }
         writeln!(fmt)?;
 
+        if !self.preamble.is_empty() {
+            writeln!(fmt)?;
+            for line in &self.preamble {
+                writeln!(fmt, "{line}")?;
+            }
+        }
+
+        if self.group_includes {
+            self.fmt_grouped_includes(fmt)?;
+        }
+
+        let mut protos_done = false;
         for item in self.items.iter() {
+            if self.group_includes && matches!(item, Item::Include(_)) {
+                continue;
+            }
+
+            if self.prototypes_first && !only_decls {
+                if let Item::Function(v) = item {
+                    if !protos_done {
+                        self.fmt_prototypes(fmt)?;
+                        protos_done = true;
+                    }
+                    writeln!(fmt)?;
+                    v.fmt_def(fmt)?;
+                    continue;
+                }
+            }
+
             writeln!(fmt)?;
             match &item {
                 Item::Comment(v) => v.fmt(fmt)?,
@@ -287,14 +671,21 @@ impl Scope {
                 Item::Struct(v) => v.fmt(fmt)?,
                 Item::Macro(v) => v.fmt(fmt)?,
                 Item::Enum(v) => v.fmt(fmt)?,
-                Item::Variable(v) => v.fmt(fmt)?,
+                Item::Variable(v) => v.do_fmt(fmt, only_decls)?,
                 Item::IfDef(v) => v.do_fmt(fmt, only_decls)?,
+                Item::Namespace(v) => v.do_fmt(fmt, only_decls)?,
                 Item::Union(v) => v.fmt(fmt)?,
+                Item::TaggedUnion(v) => v.fmt(fmt)?,
+                Item::StaticAssert(v) => v.fmt(fmt)?,
                 Item::Function(v) => v.do_fmt(fmt, only_decls)?,
                 Item::Class(v) => v.do_fmt(fmt, only_decls)?,
                 Item::TypeDef(ty, name) => {
-                    writeln!(fmt, "typedef {ty} {name};")?;
+                    write!(fmt, "typedef ")?;
+                    ty.fmt(fmt)?;
+                    writeln!(fmt, " {name};")?;
                 }
+                Item::RegionBegin(name) => writeln!(fmt, "#pragma region {name}")?,
+                Item::RegionEnd => writeln!(fmt, "#pragma endregion")?,
                 Item::NewLine => writeln!(fmt)?,
             }
         }
@@ -302,11 +693,158 @@ impl Scope {
         Ok(())
     }
 
+    /// obtains the types used by the items of the scope
+    fn used_types(&self) -> Vec<Type> {
+        let mut ret = Vec::new();
+        for item in self.items.iter() {
+            match item {
+                Item::Struct(v) => ret.extend(v.used_types()),
+                Item::Union(v) => ret.extend(v.used_types()),
+                Item::TaggedUnion(v) => ret.extend(v.used_types()),
+                Item::Function(v) => ret.extend(v.used_types()),
+                Item::Class(v) => ret.extend(v.used_types()),
+                Item::Variable(v) => ret.push(v.to_type()),
+                Item::TypeDef(ty, _) => ret.push(ty.clone()),
+                Item::IfDef(v) => {
+                    for s in v.scopes() {
+                        ret.extend(s.used_types());
+                    }
+                }
+                Item::Namespace(v) => {
+                    for s in v.scopes() {
+                        ret.extend(s.used_types());
+                    }
+                }
+                _ => (),
+            }
+        }
+        ret
+    }
+
+    /// collects the symbols defined by the scope into a manifest
+    ///
+    /// Symbols inside namespaces are qualified with the namespace name.
+    pub fn symbol_manifest(&self) -> Manifest {
+        let mut m = Manifest::new(self.file.as_deref());
+        self.collect_symbols("", &mut m);
+        m
+    }
+
+    /// collects the symbols of the scope, qualified with the given prefix
+    fn collect_symbols(&self, prefix: &str, m: &mut Manifest) {
+        for item in self.items.iter() {
+            match item {
+                Item::Function(v) => {
+                    m.push_function(&format!("{prefix}{}", v.name()));
+                }
+                Item::Enum(v) => {
+                    m.push_type(&format!("{prefix}{}", v.name()));
+                }
+                Item::Struct(v) => {
+                    m.push_type(&format!("{prefix}{}", v.name()));
+                }
+                Item::Union(v) => {
+                    m.push_type(&format!("{prefix}{}", v.name()));
+                }
+                Item::TaggedUnion(v) => {
+                    m.push_type(&format!("{prefix}{}", v.name()));
+                }
+                Item::Class(v) => {
+                    m.push_type(&format!("{prefix}{}", v.name()));
+                }
+                Item::TypeDef(_, name) => {
+                    m.push_type(&format!("{prefix}{name}"));
+                }
+                Item::Variable(v) => {
+                    m.push_variable(&format!("{prefix}{}", v.name()));
+                }
+                Item::IfDef(v) => {
+                    for s in v.scopes() {
+                        s.collect_symbols(prefix, m);
+                    }
+                }
+                Item::Namespace(v) => {
+                    let prefix = format!("{prefix}{}::", v.name());
+                    for s in v.scopes() {
+                        s.collect_symbols(&prefix, m);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// formats the prototypes of all functions in the scope
+    fn fmt_prototypes(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        for item in self.items.iter() {
+            if let Item::Function(v) = item {
+                writeln!(fmt)?;
+                v.fmt_decl(fmt)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// formats the includes of the scope deduplicated and sorted in groups
+    fn fmt_grouped_includes(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        let mut includes: Vec<&Include> = self
+            .items
+            .iter()
+            .filter_map(|i| match i {
+                Item::Include(v) => Some(v),
+                _ => None,
+            })
+            .collect();
+        includes.sort_by(|a, b| {
+            (a.group(), a.path(), a.is_system()).cmp(&(b.group(), b.path(), b.is_system()))
+        });
+        includes.dedup_by(|a, b| a.path() == b.path() && a.is_system() == b.is_system());
+
+        let mut group = None;
+        for inc in includes {
+            if group != Some(inc.group()) {
+                writeln!(fmt)?;
+                group = Some(inc.group());
+            }
+            inc.fmt(fmt)?;
+        }
+        Ok(())
+    }
+
     /// Formats the scope using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         self.do_fmt(fmt, true)
     }
 
+    /// creates a formatter with the output settings of the scope
+    fn formatter<'a>(&self, dst: &'a mut String) -> Formatter<'a> {
+        Formatter::with_dialect(dst, self.dialect)
+            .with_line_ending(self.line_ending)
+            .with_left_aligned_directives(self.left_align_directives)
+            .with_delete_reasons(self.delete_reasons)
+            .with_gnu_extensions(self.gnu_extensions)
+            .with_line_width(self.line_width)
+    }
+
+    /// formats the scope with the output settings of the scope, as written by `to_file`
+    fn format(&self, only_decls: bool) -> String {
+        let mut ret = String::new();
+        let mut fmt = self.formatter(&mut ret);
+        self.do_fmt(&mut fmt, only_decls).unwrap();
+        ret
+    }
+
+    /// formats the scope including the definitions, i.e., the content of the source file
+    ///
+    /// This is the same output `to_file(path, false)` writes, while the `Display`
+    /// implementation only emits the declarations.
+    pub fn to_string_with_defs(&self) -> String {
+        self.format(false)
+    }
+
+    /// writes the scope into its file below `path`, creating missing directories
+    ///
+    /// Errors carry the path of the file that could not be written.
     pub fn to_file(&self, path: &Path, only_decls: bool) -> std::io::Result<()> {
         // set the path to the file
         let file = if let Some(f) = &self.file {
@@ -315,11 +853,35 @@ impl Scope {
             path.join("file.c")
         };
 
-        let mut ret = String::new();
-        self.do_fmt(&mut Formatter::new(&mut ret), only_decls).unwrap();
+        let ret = self.format(only_decls);
+
+        // create the parent directories and write the file, return IOError otherwise
+        let with_path =
+            |e: std::io::Error| std::io::Error::new(e.kind(), format!("{}: {e}", file.display()));
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent).map_err(with_path)?;
+        }
+        fs::write(&file, ret.as_bytes()).map_err(with_path)
+    }
 
-        // write the file, return IOError otherwise
-        fs::write(file, ret.as_bytes())
+    /// writes the symbol manifest as JSON next to the generated file
+    ///
+    /// # Example
+    ///
+    /// `foo.c` => `foo.c.json`
+    pub fn write_manifest(&self, path: &Path) -> std::io::Result<()> {
+        let file = if let Some(f) = &self.file {
+            path.join(format!("{f}.json"))
+        } else {
+            path.join("file.c.json")
+        };
+
+        let with_path =
+            |e: std::io::Error| std::io::Error::new(e.kind(), format!("{}: {e}", file.display()));
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent).map_err(with_path)?;
+        }
+        fs::write(&file, self.symbol_manifest().to_json().as_bytes()).map_err(with_path)
     }
 }
 
@@ -332,11 +894,12 @@ impl Default for Scope {
 impl fmt::Display for Scope {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut ret = String::new();
-        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        let mut fmt = self.formatter(&mut ret);
+        self.fmt(&mut fmt).unwrap();
 
         // Remove the trailing newline
-        if ret.as_bytes().last() == Some(&b'\n') {
-            ret.pop();
+        if ret.ends_with(self.line_ending.as_str()) {
+            ret.truncate(ret.len() - self.line_ending.as_str().len());
         }
 
         write!(f, "{ret}")
@@ -0,0 +1,83 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2022 Reto Achermann
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Static Assertions
+//!
+//! This module defines compile-time assertions, e.g., to verify the layout of
+//! structs: `_Static_assert(cond, "msg");` in C and `static_assert(cond, "msg");`
+//! in C++.
+
+// std includes
+use std::fmt::{self, Display, Write};
+
+use crate::{Expr, Formatter};
+
+/// defines a compile-time assertion
+#[derive(Debug, Clone)]
+pub struct StaticAssert {
+    /// the condition that must hold
+    cond: Expr,
+    /// the message emitted when the condition does not hold
+    msg: String,
+}
+
+impl StaticAssert {
+    /// creates a new static assertion with the given condition and message
+    pub fn new(cond: Expr, msg: &str) -> Self {
+        StaticAssert {
+            cond,
+            msg: String::from(msg),
+        }
+    }
+
+    /// obtains the condition of the assertion
+    pub fn cond(&self) -> &Expr {
+        &self.cond
+    }
+
+    /// obtains the message of the assertion
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    /// formats the static assertion
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if fmt.dialect().is_cpp() {
+            write!(fmt, "static_assert(")?;
+        } else {
+            write!(fmt, "_Static_assert(")?;
+        }
+        self.cond.fmt(fmt)?;
+        writeln!(fmt, ", {});", Expr::new_str(&self.msg))
+    }
+}
+
+impl Display for StaticAssert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ret = String::new();
+        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        write!(f, "{ret}")
+    }
+}
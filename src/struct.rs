@@ -33,7 +33,7 @@
 
 use std::fmt::{self, Display, Write};
 
-use crate::{Doc, Field, Formatter, Type};
+use crate::{Doc, Expr, Field, Formatter, StaticAssert, Type};
 
 ///defines a struct
 #[derive(Debug, Clone)]
@@ -74,6 +74,17 @@ impl Struct {
         }
     }
 
+    /// obtains the name of the struct
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// sets the name of the struct
+    pub fn set_name(&mut self, name: &str) -> &mut Self {
+        self.name = String::from(name);
+        self
+    }
+
     /// Returns the corresponding type reference for this struct
     ///
     /// # Example
@@ -141,6 +152,38 @@ impl Struct {
         self
     }
 
+    /// obtains the types used by the fields of the struct
+    pub(crate) fn used_types(&self) -> Vec<Type> {
+        self.fields.iter().map(|f| f.to_type()).collect()
+    }
+
+    /// computes the total number of bits of a struct consisting only of bitfields
+    ///
+    /// Returns `None` if the struct has no fields or mixes bitfields with regular fields.
+    pub fn total_bitfield_bits(&self) -> Option<usize> {
+        if self.fields.is_empty() {
+            return None;
+        }
+        self.fields.iter().map(|f| f.bitfield_width().map(usize::from)).sum()
+    }
+
+    /// creates static assertions checking the offsets of the given fields
+    ///
+    /// # Example
+    ///
+    /// `("bar", 4)` => `_Static_assert((offsetof(struct foo, bar) == 0x4), "...");`
+    pub fn offset_asserts(&self, offsets: &[(&str, u64)]) -> Vec<StaticAssert> {
+        offsets
+            .iter()
+            .map(|(field, off)| {
+                let cond =
+                    Expr::binop(Expr::offset_of(self.to_type(), field), "==", Expr::new_num(*off));
+                let msg = format!("unexpected offset of {}.{}", self.name, field);
+                StaticAssert::new(cond, &msg)
+            })
+            .collect()
+    }
+
     /// Formats a forward declaration for the struct
     pub fn fmt_decl(&self, fmt: &mut Formatter) -> fmt::Result {
         write!(fmt, "struct {};   // forward declaration", self.name)
@@ -157,8 +200,11 @@ impl Struct {
         // consider this as a forward declaration
         if !self.fields.is_empty() {
             fmt.block(|fmt| {
+                // align the trailing comments of the fields
+                let width = self.fields.iter().map(|f| f.decl_width()).max().unwrap_or(0);
+                let col = fmt.get_indent() + width + 2;
                 for field in &self.fields {
-                    field.fmt(fmt)?;
+                    field.do_fmt(fmt, Some(col))?;
                 }
                 Ok(())
             })?;
@@ -30,14 +30,17 @@
 // std includes
 use std::fmt::{self, Display, Write};
 
-use crate::{Block, Expr, Formatter};
+use crate::{Block, Enum, Expr, Formatter, Variant};
+
+/// the maximum number of labels a case range is expanded to without GNU extensions
+const MAX_EXPANDED_RANGE: u64 = 256;
 
 #[derive(Debug, Clone)]
 pub struct Switch {
     /// the conditional of the if-else block
     cond: Expr,
-    /// the cases of this switch statement
-    cases: Vec<(Expr, Block)>,
+    /// the cases of this switch statement, with an optional upper bound for case ranges
+    cases: Vec<(Expr, Option<Expr>, Block)>,
     /// the default branch
     default: Option<Block>,
 }
@@ -52,6 +55,23 @@ impl Switch {
         }
     }
 
+    /// creates a switch over the enum with one case per variant
+    ///
+    /// The closure builds the body of each case, e.g., ending in `break;` or a
+    /// `return`. No default case is added, so the compiler can warn about missing
+    /// variants if the enum is extended later.
+    ///
+    /// # Example
+    ///
+    /// `enum color { RED }` => `switch (c) { case RED: ... }`
+    pub fn from_enum_with(cond: &Expr, e: &Enum, mut f: impl FnMut(&Variant) -> Block) -> Self {
+        let mut s = Switch::new(cond);
+        for v in e.variants() {
+            s.case(Expr::new_var(v.name(), e.to_type()), f(v));
+        }
+        s
+    }
+
     /// sets the then branch of the conditional
     pub fn set_default(&mut self, default: Block) -> &mut Self {
         self.default = Some(default);
@@ -59,9 +79,15 @@ impl Switch {
     }
 
     /// obtains a mutable reference to the then branch of the conditional
+    ///
+    /// # Panics
+    ///
+    /// The label must be an integral constant expression, see `Expr::is_integral_const`,
+    /// e.g., a string or floating point label panics.
     pub fn new_case(&mut self, label: Expr) -> &mut Block {
-        self.cases.push((label, Block::new()));
-        if let Some((_, block)) = self.cases.last_mut() {
+        assert!(label.is_integral_const(), "invalid case label: {label}");
+        self.cases.push((label, None, Block::new()));
+        if let Some((_, _, block)) = self.cases.last_mut() {
             block
         } else {
             unreachable!()
@@ -69,18 +95,89 @@ impl Switch {
     }
 
     /// obtains a mutable reference to the else branch of the conditional
+    ///
+    /// # Panics
+    ///
+    /// The label must be an integral constant expression, see `Expr::is_integral_const`.
     pub fn case(&mut self, label: Expr, block: Block) -> &mut Self {
-        self.cases.push((label, block));
+        assert!(label.is_integral_const(), "invalid case label: {label}");
+        self.cases.push((label, None, block));
         self
     }
 
+    /// adds a new case covering the inclusive range `lo ... hi` and returns its block
+    ///
+    /// # Example
+    ///
+    /// `1`, `5` => `case 0x1 ... 0x5:`
+    ///
+    /// Case ranges are a GNU extension, see `Scope::set_gnu_extensions`. Without it,
+    /// ranges of up to 256 numeric constants are expanded into one label per value,
+    /// other ranges are emitted with the GNU syntax regardless.
+    ///
+    /// # Panics
+    ///
+    /// Both bounds must be integral constant expressions.
+    pub fn new_case_range(&mut self, lo: Expr, hi: Expr) -> &mut Block {
+        assert!(lo.is_integral_const(), "invalid case label: {lo}");
+        assert!(hi.is_integral_const(), "invalid case label: {hi}");
+        self.cases.push((lo, Some(hi), Block::new()));
+        if let Some((_, _, block)) = self.cases.last_mut() {
+            block
+        } else {
+            unreachable!()
+        }
+    }
+
+    /// obtains the expressions of the switch statement, i.e., the condition
+    pub(crate) fn exprs(&self) -> Vec<&Expr> {
+        vec![&self.cond]
+    }
+
+    /// obtains the nested blocks of the switch statement
+    pub(crate) fn blocks(&self) -> Vec<&Block> {
+        let mut ret: Vec<&Block> = self.cases.iter().map(|(_, _, b)| b).collect();
+        if let Some(d) = &self.default {
+            ret.push(d);
+        }
+        ret
+    }
+
+    /// formats the labels of a case range, expanding numeric ranges into one label per
+    /// value if the GNU extensions are not available
+    fn fmt_range(lo: &Expr, hi: &Expr, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let (false, Expr::ConstNum(l), Expr::ConstNum(h)) = (fmt.gnu_extensions(), lo, hi) {
+            if h.saturating_sub(*l) < MAX_EXPANDED_RANGE {
+                for v in *l..=*h {
+                    write!(fmt, "case ")?;
+                    Expr::new_num(v).fmt(fmt)?;
+                    writeln!(fmt, ":")?;
+                }
+                return Ok(());
+            }
+        }
+
+        write!(fmt, "case ")?;
+        lo.fmt(fmt)?;
+        write!(fmt, " ... ")?;
+        hi.fmt(fmt)?;
+        writeln!(fmt, ":")
+    }
+
     /// formats the conditional
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         write!(fmt, "switch (")?;
         self.cond.fmt(fmt)?;
         writeln!(fmt, ") {{")?;
-        for (label, block) in self.cases.iter() {
-            writeln!(fmt, "case {}:", label)?;
+        for (label, hi, block) in self.cases.iter() {
+            match hi {
+                Some(hi) => Self::fmt_range(label, hi, fmt)?,
+                None => {
+                    write!(fmt, "case ")?;
+                    label.fmt(fmt)?;
+                    writeln!(fmt, ":")?;
+                }
+            }
             fmt.block(|f| block.fmt(f))?;
             writeln!(fmt, "\nbreak;")?;
         }
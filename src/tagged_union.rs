@@ -0,0 +1,173 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2022 Reto Achermann
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Tagged Unions
+//!
+//! This module defines a tagged union, a common C pattern that combines an enum
+//! discriminant with an anonymous union of the variant payloads in a struct.
+//!
+//! ## Example
+//!
+//! ```c
+//! enum shape_kind {
+//!     SHAPE_CIRCLE,
+//!     SHAPE_RECT
+//! };
+//! struct shape {
+//!     enum shape_kind kind;
+//!     union {
+//!         struct circle circle;
+//!         struct rect rect;
+//!     };
+//! };
+//! ```
+
+use std::fmt::{self, Display, Write};
+
+use crate::{Doc, Enum, Field, Formatter, Type, Union};
+
+/// defines a tagged union
+#[derive(Debug, Clone)]
+pub struct TaggedUnion {
+    /// the name of the tagged union struct
+    name: String,
+
+    /// the enum holding the discriminant values
+    tag: Enum,
+
+    /// the name of the discriminant field
+    tag_field: String,
+
+    /// the anonymous union holding the payloads
+    payload: Union,
+
+    /// the documentation for this tagged union
+    doc: Option<Doc>,
+}
+
+impl TaggedUnion {
+    /// Returns a new `TaggedUnion` instance with the given name.
+    ///
+    /// The discriminant enum is named `<name>_kind`, and the discriminant field `kind`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: String::from(name),
+            tag: Enum::new(&format!("{name}_kind")),
+            tag_field: String::from("kind"),
+            payload: Union::new(""),
+            doc: None,
+        }
+    }
+
+    /// obtains the name of the tagged union
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the corresponding type reference for this tagged union
+    ///
+    /// # Example
+    ///
+    /// struct Foo {}  => struct Foo;
+    pub fn to_type(&self) -> Type {
+        Type::new_struct(&self.name)
+    }
+
+    /// Returns the type of the discriminant enum
+    pub fn tag_type(&self) -> Type {
+        self.tag.to_type()
+    }
+
+    /// Adds a new documentation to the tagged union
+    pub fn doc(&mut self, doc: Doc) -> &mut Self {
+        self.doc = Some(doc);
+        self
+    }
+
+    /// Adds a new doc string to the tagged union
+    pub fn push_doc_str(&mut self, doc: &str) -> &mut Self {
+        if let Some(d) = &mut self.doc {
+            d.add_text(doc);
+        } else {
+            self.doc = Some(Doc::with_str(doc));
+        }
+        self
+    }
+
+    /// sets the name of the discriminant field
+    pub fn set_tag_field(&mut self, name: &str) -> &mut Self {
+        self.tag_field = String::from(name);
+        self
+    }
+
+    /// adds a new variant with the given payload type
+    ///
+    /// The discriminant value is named `<NAME>_<VARIANT>` in upper case, and the
+    /// payload field is named after the variant.
+    pub fn new_variant(&mut self, name: &str, ty: Type) -> &mut Field {
+        let tag = format!("{}_{}", self.name, name).to_uppercase();
+        self.tag.new_variant(&tag);
+        self.payload.new_field(name, ty)
+    }
+
+    /// obtains a reference to the discriminant enum
+    pub fn tag_enum(&self) -> &Enum {
+        &self.tag
+    }
+
+    /// obtains a reference to the payload union
+    pub fn payload_union(&self) -> &Union {
+        &self.payload
+    }
+
+    /// obtains the types used by the payload of the tagged union
+    pub(crate) fn used_types(&self) -> Vec<Type> {
+        self.payload.used_types()
+    }
+
+    /// Formats the tagged union using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.tag.fmt(fmt)?;
+
+        if let Some(ref docs) = self.doc {
+            docs.fmt(fmt)?;
+        }
+
+        write!(fmt, "struct {}", self.name)?;
+        fmt.block(|fmt| {
+            Field::new(&self.tag_field, self.tag.to_type()).fmt(fmt)?;
+            self.payload.fmt(fmt)
+        })?;
+        writeln!(fmt, ";")
+    }
+}
+
+impl Display for TaggedUnion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ret = String::new();
+        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        write!(f, "{ret}")
+    }
+}
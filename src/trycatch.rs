@@ -0,0 +1,118 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2022 Reto Achermann
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Try-Catch
+//!
+//! This module defines the C++ exception handling block `try { ... } catch (...) { ... }`
+
+// std includes
+use std::fmt::{self, Display, Write};
+
+use crate::{Block, Formatter, MethodParam};
+
+#[derive(Debug, Clone)]
+pub struct TryCatch {
+    /// the body of the try block
+    body: Block,
+    /// the exception handlers, a handler without parameter catches all exceptions
+    handlers: Vec<(Option<MethodParam>, Block)>,
+}
+
+impl TryCatch {
+    /// creates a new, empty try-catch block
+    pub fn new() -> Self {
+        TryCatch {
+            body: Block::new(),
+            handlers: Vec::new(),
+        }
+    }
+
+    /// sets the body of the try block
+    pub fn set_body(&mut self, body: Block) -> &mut Self {
+        self.body = body;
+        self
+    }
+
+    /// obtains a mutable reference to the body of the try block
+    pub fn body(&mut self) -> &mut Block {
+        &mut self.body
+    }
+
+    /// adds a new handler catching the given exception parameter and returns its block
+    pub fn new_catch(&mut self, param: MethodParam) -> &mut Block {
+        self.handlers.push((Some(param), Block::new()));
+        &mut self.handlers.last_mut().unwrap().1
+    }
+
+    /// adds a new handler catching all exceptions `catch (...)` and returns its block
+    pub fn new_catch_all(&mut self) -> &mut Block {
+        self.handlers.push((None, Block::new()));
+        &mut self.handlers.last_mut().unwrap().1
+    }
+
+    /// adds a handler to the try-catch block
+    pub fn catch(&mut self, param: Option<MethodParam>, block: Block) -> &mut Self {
+        self.handlers.push((param, block));
+        self
+    }
+
+    /// obtains the nested blocks of the try-catch block
+    pub(crate) fn blocks(&self) -> Vec<&Block> {
+        let mut ret = vec![&self.body];
+        ret.extend(self.handlers.iter().map(|(_, b)| b));
+        ret
+    }
+
+    /// formats the try-catch block
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "try")?;
+        fmt.block(|f| self.body.fmt(f))?;
+        for (param, block) in self.handlers.iter() {
+            write!(fmt, " catch (")?;
+            if let Some(p) = param {
+                p.fmt(fmt)?;
+            } else {
+                write!(fmt, "...")?;
+            }
+            write!(fmt, ")")?;
+            fmt.block(|f| block.fmt(f))?;
+        }
+        writeln!(fmt)
+    }
+}
+
+impl Default for TryCatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for TryCatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ret = String::new();
+        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        write!(f, "{ret}")
+    }
+}
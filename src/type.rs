@@ -32,6 +32,7 @@ use std::fmt::{self, Display, Write};
 
 // the formatter
 use crate::formatter::Formatter;
+use crate::Expr;
 
 /// Represents the visibility for C++ class members
 #[derive(Debug, Clone, PartialEq, Copy)]
@@ -76,8 +77,16 @@ pub enum BaseType {
     Double,
     /// single precision floating point number.
     Float,
+    /// extended precision floating point number. (`long double`)
+    LongDouble,
     /// a character
     Char,
+    /// an explicitly signed character (`signed char`)
+    SignedChar,
+    /// an unsigned character (`unsigned char`)
+    UnsignedChar,
+    /// the native signed integer (`int`)
+    Int,
     /// an unsigned one byte integer. (`uint8_t`)
     UInt8,
     /// an unsigned two byte integer. (`uint16_t`)
@@ -108,10 +117,12 @@ pub enum BaseType {
     Union(String),
     /// a simple class
     Class(String),
-    /// a class with tempaltes
-    TemplateClass(String, Vec<String>),
+    /// a template instantiation with type, non-type, or template arguments
+    TemplateClass(String, Vec<TemplateArg>),
     /// a typedef `foo_t`
     TypeDef(String, bool),
+    /// a function pointer with return type and parameter types `ret (*)(params)`
+    FnPtr(Box<Type>, Vec<Type>),
 }
 
 impl BaseType {
@@ -122,7 +133,11 @@ impl BaseType {
             Void => write!(fmt, "void"),
             Double => write!(fmt, "double"),
             Float => write!(fmt, "float"),
+            LongDouble => write!(fmt, "long double"),
             Char => write!(fmt, "char"),
+            SignedChar => write!(fmt, "signed char"),
+            UnsignedChar => write!(fmt, "unsigned char"),
+            Int => write!(fmt, "int"),
             UInt8 => write!(fmt, "uint8_t"),
             UInt16 => write!(fmt, "uint16_t"),
             UInt32 => write!(fmt, "uint32_t"),
@@ -138,23 +153,52 @@ impl BaseType {
             Struct(s) => write!(fmt, "struct {s}"),
             Union(s) => write!(fmt, "union {s}"),
             Class(s) => write!(fmt, "{s}"),
-            TemplateClass(s, t) => {
-                if !t.is_empty() {
-                    write!(fmt, "{}<{}>", s, t.join(","))
-                } else {
-                    write!(fmt, "{s}")
+            TemplateClass(s, args) if args.is_empty() => write!(fmt, "{s}"),
+            TemplateClass(s, args) => {
+                write!(fmt, "{s}<")?;
+                for (i, a) in args.iter().enumerate() {
+                    if i != 0 {
+                        write!(fmt, ", ")?;
+                    }
+                    a.fmt(fmt)?;
                 }
+                write!(fmt, ">")
             }
             TypeDef(s, _) => write!(fmt, "{s}"),
+            FnPtr(ret, params) => {
+                ret.as_ref().fmt(fmt)?;
+                write!(fmt, " (*)")?;
+                BaseType::fmt_fn_params(params, fmt)
+            }
         }
     }
 
+    /// formats the parameter list of a function pointer, `(void)` in C and `()` in C++
+    fn fmt_fn_params(params: &[Type], fmt: &mut Formatter<'_>) -> fmt::Result {
+        if params.is_empty() {
+            if fmt.dialect().is_cpp() {
+                return write!(fmt, "()");
+            }
+            return write!(fmt, "(void)");
+        }
+
+        write!(fmt, "(")?;
+        for (i, p) in params.iter().enumerate() {
+            if i != 0 {
+                write!(fmt, ", ")?;
+            }
+            p.fmt(fmt)?;
+        }
+        write!(fmt, ")")
+    }
+
     /// checks if the base type is an integer type
     pub fn is_integer(&self) -> bool {
         use BaseType::*;
         matches!(self, |UInt8| UInt16  | UInt32  | UInt64
             | Int8  | Int16   | Int32   | Int64
             | Size  | UIntPtr | Bool    | Char
+            | SignedChar | UnsignedChar | Int
             // allowing the typedef here
             | TypeDef(_, false))
     }
@@ -164,6 +208,31 @@ impl BaseType {
         matches!(self, Struct(_) | Union(_) | Class(_) | TemplateClass(_, _) | TypeDef(_, _))
     }
 
+    /// obtains the standard include that declares the base type, if any
+    ///
+    /// # Example
+    ///
+    /// `uint32_t` => `stdint.h`, `std::string` => `string`
+    pub fn required_include(&self) -> Option<&'static str> {
+        use BaseType::*;
+        match self {
+            UInt8 | UInt16 | UInt32 | UInt64 | Int8 | Int16 | Int32 | Int64 | UIntPtr => {
+                Some("stdint.h")
+            }
+            Size => Some("stddef.h"),
+            Bool => Some("stdbool.h"),
+            Class(s) | TemplateClass(s, _) => match s.split('<').next().unwrap_or_default() {
+                "std::string" => Some("string"),
+                "std::vector" => Some("vector"),
+                "std::map" => Some("map"),
+                "std::array" => Some("array"),
+                "std::unique_ptr" | "std::shared_ptr" => Some("memory"),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     /// creates a new unsigned integer type with a given type
     pub fn new_uint(bits: u64) -> BaseType {
         use BaseType::*;
@@ -202,6 +271,41 @@ impl Display for BaseType {
     }
 }
 
+/// Represents an argument of a template instantiation
+#[derive(Debug, Clone)]
+pub enum TemplateArg {
+    /// a type argument, e.g., `uint8_t`
+    Type(Type),
+    /// a non-type argument, e.g., `16`
+    Value(Expr),
+    /// a template template argument, e.g., `std::allocator`
+    Template(String),
+}
+
+impl TemplateArg {
+    /// creates a new non-type argument from a number, printed in decimal
+    pub fn new_num(n: u64) -> Self {
+        TemplateArg::Value(Expr::Raw(n.to_string()))
+    }
+
+    /// formats the template argument into the supplied formatter
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateArg::Type(t) => t.fmt(fmt),
+            TemplateArg::Value(e) => e.fmt(fmt),
+            TemplateArg::Template(s) => write!(fmt, "{s}"),
+        }
+    }
+}
+
+impl Display for TemplateArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ret = String::new();
+        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        write!(f, "{ret}")
+    }
+}
+
 /// the type modifiers
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TypeModifier {
@@ -213,6 +317,10 @@ pub enum TypeModifier {
     Const,
     /// represents a reference type
     Ref,
+    /// represents an rvalue reference type
+    RvalueRef,
+    /// represents a restrict qualified pointer
+    Restrict,
 }
 
 /// The `Type` corresponds to a full type. This is a base type with modifiers.
@@ -241,6 +349,10 @@ impl TypeModifier {
             Volatile => write!(fmt, " volatile"),
             Const => write!(fmt, " const"),
             Ref => write!(fmt, " &"),
+            RvalueRef => write!(fmt, " &&"),
+            // C++ has no `restrict` keyword, the compilers support `__restrict`
+            Restrict if fmt.dialect().is_cpp() => write!(fmt, " __restrict"),
+            Restrict => write!(fmt, " restrict"),
         }
     }
 }
@@ -268,16 +380,46 @@ impl Type {
         Type::new(BaseType::Bool)
     }
 
+    /// creates a new type description for single precision floating point numbers
+    pub fn new_float() -> Self {
+        Type::new(BaseType::Float)
+    }
+
+    /// creates a new type description for double precision floating point numbers
+    pub fn new_double() -> Self {
+        Type::new(BaseType::Double)
+    }
+
+    /// creates a new type description for extended precision floating point numbers
+    pub fn new_long_double() -> Self {
+        Type::new(BaseType::LongDouble)
+    }
+
     /// creates a new type description for characters
     pub fn new_char() -> Self {
         Type::new(BaseType::Char)
     }
 
+    /// creates a new type description for explicitly signed characters
+    pub fn new_signed_char() -> Self {
+        Type::new(BaseType::SignedChar)
+    }
+
+    /// creates a new type description for unsigned characters
+    pub fn new_unsigned_char() -> Self {
+        Type::new(BaseType::UnsignedChar)
+    }
+
     /// creates a new type description for signed integers
     pub fn new_int(bits: u64) -> Self {
         Type::new(BaseType::new_int(bits))
     }
 
+    /// creates a new type description for the native signed integer, `int`
+    pub fn new_plain_int() -> Self {
+        Type::new(BaseType::Int)
+    }
+
     /// creates an new type description for signed chars
     pub fn new_int8() -> Self {
         Type::new(BaseType::Int8)
@@ -345,6 +487,15 @@ impl Type {
         t
     }
 
+    /// creates a new type for a function pointer with the given return and parameter types
+    ///
+    /// # Example
+    ///
+    /// `void`, `[int]` => `void (*)(int)`
+    pub fn new_fn_ptr(ret: Type, params: Vec<Type>) -> Self {
+        Type::new(BaseType::FnPtr(Box::new(ret), params))
+    }
+
     /// creates a new type for an enum
     pub fn new_enum(name: &str) -> Self {
         Type::new(BaseType::Enum(String::from(name)))
@@ -365,6 +516,15 @@ impl Type {
         Type::new(BaseType::Class(String::from(name)))
     }
 
+    /// creates a new type for a template instantiation
+    ///
+    /// # Example
+    ///
+    /// `std::array`, `[uint8_t, 16]` => `std::array<uint8_t, 16>`
+    pub fn new_template(name: &str, args: Vec<TemplateArg>) -> Self {
+        Type::new(BaseType::TemplateClass(String::from(name), args))
+    }
+
     /// creates a new type for a given typedef
     pub fn new_typedef(name: &str) -> Self {
         Type::new(BaseType::TypeDef(name.to_string(), false))
@@ -394,10 +554,63 @@ impl Type {
     /// `int` => `int &`
     pub fn to_ref(&self) -> Self {
         let mut n = self.clone();
-        n.mods.push(TypeModifier::Ref);
+        n.reference();
+        n
+    }
+
+    /// creates a new type from `self` by taking an rvalue reference of it
+    ///
+    /// # Example
+    ///
+    /// `int` => `int &&`
+    pub fn to_rvalue_ref(&self) -> Self {
+        let mut n = self.clone();
+        n.rvalue_reference();
+        n
+    }
+
+    /// creates a new type from `self` by taking a const reference of it
+    ///
+    /// The `const` applies to the referenced type, i.e., the pointer itself for
+    /// pointer types. References collapse as in C++ templates, i.e., taking the
+    /// const reference of a reference type yields an lvalue reference.
+    ///
+    /// # Example
+    ///
+    /// `std::string` => `const std::string &`, `int *` => `int * const &`
+    ///
+    /// `int &` => `int &`, `int &&` => `int &`
+    pub fn const_ref(&self) -> Self {
+        let mut n = self.clone();
+        if n.is_ref() {
+            if let Some(m @ TypeModifier::RvalueRef) = n.mods.last_mut() {
+                *m = TypeModifier::Ref;
+            }
+            return n;
+        }
+
+        if n.mods.is_empty() {
+            n.is_const = true;
+        } else {
+            n.constant();
+        }
+        n.reference();
         n
     }
 
+    /// creates a new type from `self` by taking an rvalue reference of it, the
+    /// counterpart of `const_ref` for move parameters
+    ///
+    /// This is an alias of `to_rvalue_ref`, so the parameter helpers read as a pair,
+    /// e.g., `ty.const_ref()` for the copy and `ty.rvalue_ref()` for the move overload.
+    ///
+    /// # Example
+    ///
+    /// `std::string` => `std::string &&`
+    pub fn rvalue_ref(&self) -> Self {
+        self.to_rvalue_ref()
+    }
+
     /// obtais a new type from `self` by dereferencing the pointer
     ///
     /// # Example
@@ -457,6 +670,22 @@ impl Type {
         self
     }
 
+    /// creates a new type from `self` by applying the array-to-pointer decay
+    ///
+    /// Function types are represented as function pointers and are already decayed.
+    ///
+    /// # Example
+    ///
+    /// `int [10]` => `int *`, `int` => `int`
+    pub fn decay(&self) -> Self {
+        let mut n = self.clone();
+        if n.array_size != 0 {
+            n.array_size = 0;
+            n.pointer();
+        }
+        n
+    }
+
     /// obtainst the base type of the type
     pub fn basetype(&self) -> &BaseType {
         &self.base
@@ -478,6 +707,11 @@ impl Type {
         self.base.is_integer()
     }
 
+    /// returns true if this type is an enum value, i.e., not a pointer to an enum
+    pub fn is_enum(&self) -> bool {
+        self.nptr == 0 && matches!(self.base, BaseType::Enum(_))
+    }
+
     /// returns true if the type represents a pointer value
     ///
     /// Note: if the type is a typedef, this will return true.
@@ -485,6 +719,25 @@ impl Type {
         self.nptr > 0 || self.array_size != 0 || matches!(self.base, BaseType::TypeDef(_, true))
     }
 
+    /// returns true if the type is a reference, `T &` or `T &&`
+    pub fn is_ref(&self) -> bool {
+        self.mods
+            .iter()
+            .any(|m| matches!(m, TypeModifier::Ref | TypeModifier::RvalueRef))
+    }
+
+    /// returns true if the value is accessed through a pointer or a reference
+    pub(crate) fn is_indirect(&self) -> bool {
+        self.nptr > 0 || self.is_ref()
+    }
+
+    /// renames the class of the type, if the type refers to the class `from`
+    pub(crate) fn rename_class(&mut self, from: &str, to: &str) {
+        if matches!(&self.base, BaseType::Class(n) if n == from) {
+            self.base = BaseType::Class(String::from(to));
+        }
+    }
+
     /// returns true if the type represents an array value
     pub fn is_array(&self) -> bool {
         self.array_size != 0
@@ -546,9 +799,27 @@ impl Type {
     ///
     /// # Example
     ///
-    /// `int` => `int &`
+    /// `int` => `int &`, `int &` => `int &&`
     pub fn reference(&mut self) -> &mut Self {
-        self.mods.push(TypeModifier::Ref);
+        match self.mods.last_mut() {
+            Some(m @ TypeModifier::Ref) => *m = TypeModifier::RvalueRef,
+            Some(TypeModifier::RvalueRef) => (),
+            _ => self.mods.push(TypeModifier::Ref),
+        }
+        self
+    }
+
+    /// adds an rvalue reference modifier to the current type
+    ///
+    /// # Example
+    ///
+    /// `int` => `int &&`
+    pub fn rvalue_reference(&mut self) -> &mut Self {
+        match self.mods.last_mut() {
+            Some(m @ TypeModifier::Ref) => *m = TypeModifier::RvalueRef,
+            Some(TypeModifier::RvalueRef) => (),
+            _ => self.mods.push(TypeModifier::RvalueRef),
+        }
         self
     }
 
@@ -562,6 +833,26 @@ impl Type {
         self
     }
 
+    /// adds a pointer modifier that is itself const to the current type
+    ///
+    /// # Example
+    ///
+    /// `const int` => `const int * const`, `int * const` => `int * const * const`
+    pub fn ptr_const(&mut self) -> &mut Self {
+        self.pointer().constant()
+    }
+
+    /// creates a new type from `self` by taking a const pointer of it
+    ///
+    /// # Example
+    ///
+    /// `const int *` => `const int * * const`
+    pub fn to_ptr_const(&self) -> Self {
+        let mut n = self.clone();
+        n.ptr_const();
+        n
+    }
+
     /// adds a volatile modifier to the current type
     ///
     /// # Example
@@ -572,6 +863,169 @@ impl Type {
         self
     }
 
+    /// adds a restrict modifier to the current type
+    ///
+    /// # Example
+    ///
+    /// `int *` => `int * restrict` (C), `int * __restrict` (C++)
+    pub fn restrict(&mut self) -> &mut Self {
+        self.mods.push(TypeModifier::Restrict);
+        self
+    }
+
+    /// makes the value of the type const, i.e., the base type
+    ///
+    /// # Example
+    ///
+    /// `int` => `const int`, `int *` => `const int *`
+    pub fn const_value(&mut self) -> &mut Self {
+        self.set_value_const()
+    }
+
+    /// makes the value the outermost pointer points to const
+    ///
+    /// # Example
+    ///
+    /// `int *` => `const int *`, `int **` => `int * const *`
+    pub fn const_pointee(&mut self) -> &mut Self {
+        let ptrs: Vec<usize> = self
+            .mods
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| **m == TypeModifier::Ptr)
+            .map(|(i, _)| i)
+            .collect();
+
+        match ptrs.as_slice() {
+            [] | [_] => self.const_value(),
+            [.., prev, last] => {
+                if !self.mods[*prev + 1..*last].contains(&TypeModifier::Const) {
+                    self.mods.insert(*last, TypeModifier::Const);
+                }
+                self
+            }
+        }
+    }
+
+    /// makes the outermost pointer itself const
+    ///
+    /// # Example
+    ///
+    /// `int *` => `int * const`
+    pub fn const_pointer(&mut self) -> &mut Self {
+        if self.mods.last() != Some(&TypeModifier::Const) {
+            self.mods.push(TypeModifier::Const);
+        }
+        self
+    }
+
+    /// formats the declarator of the type with the given name
+    ///
+    /// # Example
+    ///
+    /// `int` => `int name`, `void (*)(void)` => `void (*name)(void)`. An empty name
+    /// emits the abstract declarator, e.g., for unnamed parameters.
+    pub(crate) fn fmt_with_name(&self, fmt: &mut Formatter<'_>, name: &str) -> fmt::Result {
+        let (ret, params) = match &self.base {
+            BaseType::FnPtr(ret, params) => (ret, params),
+            _ => {
+                self.fmt(fmt)?;
+                if name.is_empty() {
+                    return Ok(());
+                }
+                return write!(fmt, " {name}");
+            }
+        };
+
+        ret.as_ref().fmt(fmt)?;
+        write!(fmt, " (*")?;
+        for m in &self.mods {
+            m.fmt(fmt)?;
+        }
+        if !self.mods.is_empty() && !name.is_empty() {
+            write!(fmt, " ")?;
+        }
+        write!(fmt, "{name})")?;
+        BaseType::fmt_fn_params(params, fmt)
+    }
+
+    /// tests whether two types are spelled the same after canonicalization
+    ///
+    /// # Example
+    ///
+    /// `int const` and `const int` are the same, `int` and `size_t` are not
+    pub fn is_same(&self, other: &Type) -> bool {
+        self.clone().canonicalize().to_string() == other.clone().canonicalize().to_string()
+    }
+
+    /// obtains a new type from `self` without the top-level qualifiers, e.g., of a
+    /// returned value
+    ///
+    /// # Example
+    ///
+    /// `const int` => `int`, `const int * const` => `const int *`
+    pub fn to_unqualified(&self) -> Self {
+        use TypeModifier::*;
+
+        let mut n = self.clone();
+        n.canonicalize();
+        if n.mods.iter().any(|m| matches!(m, Ptr | Ref | RvalueRef)) {
+            while matches!(n.mods.last(), Some(Const | Volatile | Restrict)) {
+                n.mods.pop();
+            }
+        } else {
+            n.mods.clear();
+            n.is_const = false;
+            n.is_volatile = false;
+        }
+        n
+    }
+
+    /// removes redundant qualifiers and normalizes their order
+    ///
+    /// Qualifiers of the base type are moved before the base type, and qualifiers of
+    /// each pointer are ordered `const volatile restrict` without duplicates.
+    ///
+    /// # Example
+    ///
+    /// `int const const` => `const int`, `int * volatile const const` => `int * const volatile`
+    pub fn canonicalize(&mut self) -> &mut Self {
+        use TypeModifier::*;
+
+        let mut mods = Vec::with_capacity(self.mods.len());
+        let mut quals: Vec<TypeModifier> = Vec::new();
+        let mut is_base = true;
+        for m in self.mods.iter().chain(std::iter::once(&Ptr)) {
+            match m {
+                Const | Volatile | Restrict => {
+                    if !quals.contains(m) {
+                        quals.push(*m);
+                    }
+                    continue;
+                }
+                Ptr | Ref | RvalueRef => (),
+            }
+
+            if is_base {
+                self.is_const |= quals.contains(&Const);
+                self.is_volatile |= quals.contains(&Volatile);
+                quals.retain(|q| *q == Restrict);
+                is_base = false;
+            }
+            for q in [Const, Volatile, Restrict] {
+                if quals.contains(&q) {
+                    mods.push(q);
+                }
+            }
+            quals.clear();
+            mods.push(*m);
+        }
+        // remove the sentinel pointer
+        mods.pop();
+        self.mods = mods;
+        self
+    }
+
     /// Formats the variant using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         if self.is_volatile {
@@ -32,7 +32,7 @@
 
 use std::fmt::{self, Display, Write};
 
-use crate::{Doc, Field, Formatter, Type};
+use crate::{Doc, Expr, Field, Formatter, StaticAssert, Type};
 
 ///defines a union
 #[derive(Debug, Clone)]
@@ -78,6 +78,17 @@ impl Union {
         }
     }
 
+    /// obtains the name of the union
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// sets the name of the union
+    pub fn set_name(&mut self, name: &str) -> &mut Self {
+        self.name = String::from(name);
+        self
+    }
+
     /// Returns the corresponding type reference for this union
     ///
     /// # Example
@@ -87,6 +98,27 @@ impl Union {
         Type::new_union(&self.name)
     }
 
+    /// creates static assertions checking that the union is at least as large as each
+    /// of its members
+    ///
+    /// # Example
+    ///
+    /// `uint64_t x` => `_Static_assert((sizeof(union foo) >= sizeof(uint64_t)), "...");`
+    pub fn size_asserts(&self) -> Vec<StaticAssert> {
+        self.fields
+            .iter()
+            .map(|f| {
+                let cond = Expr::binop(
+                    Expr::size_of_type(self.to_type()),
+                    ">=",
+                    Expr::size_of_type(f.to_type()),
+                );
+                let msg = format!("union {} is smaller than member {}", self.name, f.name());
+                StaticAssert::new(cond, &msg)
+            })
+            .collect()
+    }
+
     /// Adds a new documentation to the union
     pub fn doc(&mut self, doc: Doc) -> &mut Self {
         self.doc = Some(doc);
@@ -141,6 +173,11 @@ impl Union {
         self
     }
 
+    /// obtains the types used by the fields of the union
+    pub(crate) fn used_types(&self) -> Vec<Type> {
+        self.fields.iter().map(|f| f.to_type()).collect()
+    }
+
     /// Formats a forward declaration for the union
     pub fn fmt_decl(&self, fmt: &mut Formatter) -> fmt::Result {
         write!(fmt, "union {};   // forward declaration", self.name)
@@ -152,13 +189,21 @@ impl Union {
             docs.fmt(fmt)?;
         }
 
-        write!(fmt, "union {}", self.name)?;
+        // anonymous unions, e.g., as members of a struct
+        if self.name.is_empty() {
+            write!(fmt, "union")?;
+        } else {
+            write!(fmt, "union {}", self.name)?;
+        }
 
         // consider this as a forward declaration
         if !self.fields.is_empty() {
             fmt.block(|fmt| {
+                // align the trailing comments of the fields
+                let width = self.fields.iter().map(|f| f.decl_width()).max().unwrap_or(0);
+                let col = fmt.get_indent() + width + 2;
                 for field in &self.fields {
-                    field.fmt(fmt)?;
+                    field.do_fmt(fmt, Some(col))?;
                 }
                 Ok(())
             })?;
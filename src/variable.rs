@@ -52,6 +52,15 @@ pub struct Variable {
 
     /// The documentation comment of the variant
     doc: Option<Doc>,
+
+    /// a short comment placed after the declaration
+    trailing_comment: Option<String>,
+
+    /// the `__declspec` of the variable, e.g., `dllimport`
+    declspec: Option<String>,
+
+    /// the macro selecting the DLL storage class, see `Scope::new_export_macro`
+    export_macro: Option<String>,
 }
 
 impl Variable {
@@ -69,6 +78,9 @@ impl Variable {
             is_static: false,
             is_extern: false,
             doc: None,
+            trailing_comment: None,
+            declspec: None,
+            export_macro: None,
         }
     }
 
@@ -94,9 +106,17 @@ impl Variable {
             is_static: false,
             is_extern: false,
             doc: None,
+            trailing_comment: None,
+            declspec: None,
+            export_macro: None,
         }
     }
 
+    /// obtains the name of the variable
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// obtains the type from the attribute
     pub fn to_type(&self) -> Type {
         self.ty.clone()
@@ -123,6 +143,16 @@ impl Variable {
         self
     }
 
+    /// sets a trailing comment that is placed after the variable declaration
+    ///
+    /// # Example
+    ///
+    /// `int foo;`  => `int foo; // comment`
+    pub fn trailing_comment(&mut self, comment: &str) -> &mut Self {
+        self.trailing_comment = Some(String::from(comment));
+        self
+    }
+
     /// changes the static modifier
     pub fn toggle_static(&mut self, val: bool) -> &mut Self {
         if val {
@@ -151,11 +181,48 @@ impl Variable {
         self.toggle_extern(true)
     }
 
+    /// sets the `__declspec` of the variable, e.g., `dllexport`
+    ///
+    /// The specifier is only emitted for MSVC, see `Dialect::Msvc`. Otherwise, `dllexport`
+    /// becomes `__attribute__((visibility("default")))`.
+    ///
+    /// # Example
+    ///
+    /// `int x;` => `__declspec(dllimport) int x;`
+    pub fn declspec(&mut self, spec: &str) -> &mut Self {
+        self.declspec = Some(String::from(spec));
+        self
+    }
+
+    /// sets the macro selecting the DLL storage class of the variable
+    ///
+    /// # Example
+    ///
+    /// `int x;` => `MYLIB_API int x;`
+    pub fn export_macro(&mut self, name: &str) -> &mut Self {
+        self.export_macro = Some(String::from(name));
+        self
+    }
+
     pub fn set_value(&mut self, val: Expr) -> &mut Self {
         self.value = Some(val);
         self
     }
 
+    /// obtains a reference to the initializer value of the variable
+    pub fn value(&self) -> Option<&Expr> {
+        self.value.as_ref()
+    }
+
+    /// zero-initializes the variable, using `{0}` in C and `{}` in C++
+    ///
+    /// # Example
+    ///
+    /// `struct foo x` => `struct foo x = {0};`
+    pub fn zero_init(&mut self) -> &mut Self {
+        self.set_value(Expr::zero_init())
+    }
+
     /// sets the default value of the attribute
     pub fn set_value_raw(&mut self, val: &str) -> &mut Self {
         self.set_value(Expr::Raw(String::from(val)))
@@ -172,17 +239,29 @@ impl Variable {
         if self.is_static {
             write!(fmt, "static ")?;
         }
-        self.ty.fmt(fmt)?;
-        write!(fmt, " {}", self.name)?;
+        if let Some(name) = &self.export_macro {
+            write!(fmt, "{name} ")?;
+        }
+        if let Some(spec) = &self.declspec {
+            fmt.write_declspec(spec)?;
+        }
+        self.ty.fmt_with_name(fmt, &self.name)?;
+        if self.ty.is_array() {
+            write!(fmt, "[{}]", self.ty.get_array_size())?;
+        }
 
-        if decl_only || self.value.is_none() || self.is_extern {
-            writeln!(fmt, ";")
-        } else {
+        if !(decl_only || self.is_extern) {
             if let Some(v) = &self.value {
-                write!(fmt, " = {v}")?;
+                write!(fmt, " = ")?;
+                v.fmt(fmt)?;
             }
-            writeln!(fmt, ";")
         }
+        write!(fmt, ";")?;
+
+        if let Some(c) = &self.trailing_comment {
+            write!(fmt, " // {c}")?;
+        }
+        writeln!(fmt)
     }
 
     /// formats the declaration of the variable
@@ -44,6 +44,12 @@ pub struct Variant {
 
     /// The documentation comment of the variant
     doc: Option<Doc>,
+
+    /// a short comment placed after the variant
+    trailing_comment: Option<String>,
+
+    /// whether the documentation comment is placed after the variant
+    is_trailing_doc: bool,
 }
 
 impl Variant {
@@ -58,6 +64,8 @@ impl Variant {
             name,
             value: None,
             doc: None,
+            trailing_comment: None,
+            is_trailing_doc: false,
         }
     }
 
@@ -72,6 +80,8 @@ impl Variant {
             name,
             value: Some(value),
             doc: None,
+            trailing_comment: None,
+            is_trailing_doc: false,
         }
     }
 
@@ -102,21 +112,95 @@ impl Variant {
         &self.name
     }
 
+    /// sets the name of the variant
+    pub fn set_name(&mut self, name: &str) -> &mut Self {
+        self.name = String::from(name);
+        self
+    }
+
     /// obtains the current value of the variant
     pub fn value(&self) -> Option<u64> {
         self.value
     }
 
-    /// Formats the variant using the given formatter.
-    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        if let Some(ref docs) = self.doc {
-            docs.fmt(fmt)?;
-        }
+    /// sets a trailing comment that is placed after the variant
+    ///
+    /// # Example
+    ///
+    /// `FOO,`  => `FOO,  // comment`
+    pub fn trailing_comment(&mut self, comment: &str) -> &mut Self {
+        self.trailing_comment = Some(String::from(comment));
+        self
+    }
+
+    /// sets whether the documentation comment is placed after the variant
+    ///
+    /// Note: the trailing documentation takes precedence over a trailing comment.
+    ///
+    /// # Example
+    ///
+    /// `/// doc\nFOO,`  => `FOO,  ///< doc`
+    pub fn toggle_trailing_doc(&mut self, val: bool) -> &mut Self {
+        self.is_trailing_doc = val;
+        self
+    }
+
+    /// places the documentation comment after the variant
+    pub fn set_trailing_doc(&mut self) -> &mut Self {
+        self.toggle_trailing_doc(true)
+    }
+
+    /// formats the name and value of the variant
+    fn fmt_decl(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         write!(fmt, "{}", self.name)?;
         if let Some(value) = self.value {
             write!(fmt, " = {value}")?;
         }
-
         Ok(())
     }
+
+    /// returns the number of characters of the variant declaration
+    pub(crate) fn decl_width(&self) -> usize {
+        let mut ret = String::new();
+        self.fmt_decl(&mut Formatter::new(&mut ret)).unwrap();
+        ret.chars().count()
+    }
+
+    /// formats the variant inside an enum, aligning the trailing comment to the given column
+    pub(crate) fn do_fmt(
+        &self,
+        fmt: &mut Formatter<'_>,
+        last: bool,
+        comment_col: usize,
+    ) -> fmt::Result {
+        if let Some(ref docs) = self.doc {
+            if !self.is_trailing_doc {
+                docs.fmt(fmt)?;
+            }
+        }
+        self.fmt_decl(fmt)?;
+        if !last {
+            write!(fmt, ",")?;
+        }
+        match (&self.doc, &self.trailing_comment) {
+            (Some(docs), _) if self.is_trailing_doc => {
+                fmt.pad_to(comment_col)?;
+                docs.fmt_trailing(fmt)?;
+            }
+            (_, Some(c)) => {
+                fmt.pad_to(comment_col)?;
+                write!(fmt, "// {c}")?;
+            }
+            _ => (),
+        }
+        writeln!(fmt)
+    }
+
+    /// Formats the variant using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.doc {
+            docs.fmt(fmt)?;
+        }
+        self.fmt_decl(fmt)
+    }
 }
@@ -77,3 +77,19 @@ fn test_attributes_modifiers() {
     f.set_static();
     assert_eq!(f.to_string(), "static uint8_t my_field;\n");
 }
+
+#[test]
+fn test_attributes_zero_init() {
+    let mut f = Attribute::new("origin", Type::new_struct("point"));
+    f.zero_init();
+    assert_eq!(f.to_string(), "struct point origin = {0};\n");
+
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp);
+    s.set_filename("shape.cpp");
+    s.new_class("Shape")
+        .new_attribute("origin", Type::new_struct("point"))
+        .set_static()
+        .zero_init();
+    assert!(s.to_string_with_defs().contains("struct point origin = {};"));
+}
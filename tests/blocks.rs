@@ -0,0 +1,345 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2021, 2022 Reto Achermann (The University of British Columbia)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Block Tests
+//!
+//! This module exercises the block tests
+
+use crustal::*;
+
+#[test]
+fn test_block_range_for() {
+    let items = Expr::new_var("items", Type::new_class("std::vector<int>"));
+    let mut b = Block::new();
+    b.new_range_for("e", &items)
+        .body()
+        .fn_call("consume", vec![Expr::new_var("e", Type::new_int32())]);
+    assert_eq!(b.to_string(), "for (auto& e : items) {\n    consume(e);\n}\n");
+}
+
+#[test]
+fn test_block_try_catch() {
+    let mut b = Block::new();
+    let t = b.new_try();
+    t.body().fn_call("run", vec![]);
+
+    let mut ty = Type::new_class("std::exception");
+    ty.set_value_const().reference();
+    t.new_catch(MethodParam::new("e", ty)).fn_call("report", vec![]);
+    assert_eq!(
+        b.to_string(),
+        "try {\n    run();\n} catch (const std::exception & e) {\n    report();\n}\n"
+    );
+}
+
+#[test]
+fn test_block_try_catch_all() {
+    let mut b = Block::new();
+    let t = b.new_try();
+    t.body().fn_call("run", vec![]);
+    t.new_catch_all().return_none();
+    assert_eq!(b.to_string(), "try {\n    run();\n} catch (...) {\n    return;\n}\n");
+}
+
+#[test]
+fn test_block_throw() {
+    let mut b = Block::new();
+    b.throw(Expr::fn_call("std::runtime_error", vec![Expr::new_str("msg")]));
+    assert_eq!(b.to_string(), "throw std::runtime_error(\"msg\");\n");
+}
+
+#[test]
+fn test_block_rethrow() {
+    let mut b = Block::new();
+    b.rethrow();
+    assert_eq!(b.to_string(), "throw;\n");
+}
+
+#[test]
+fn test_block_assert() {
+    let p = Expr::new_var("p", Type::new_int32().to_ptr());
+    let mut b = Block::new();
+    b.assert(Expr::not_null(p));
+    assert_eq!(b.to_string(), "assert((p != NULL));\n");
+    assert_eq!(b.required_includes(Dialect::C), vec!["assert.h"]);
+
+    let mut b = Block::new();
+    b.new_while_loop(&Expr::btrue()).body().assert(Expr::bfalse());
+    assert_eq!(b.required_includes(Dialect::C), vec!["assert.h"]);
+    assert!(Block::new().required_includes(Dialect::C).is_empty());
+}
+
+#[test]
+fn test_block_memcpy_memset() {
+    let dst = Expr::new_var("dst", Type::new_struct("hdr"));
+    let src = Expr::new_var("src", Type::new_struct("hdr").to_ptr());
+
+    let mut b = Block::new();
+    b.memcpy(dst.addr_of(), src.clone(), dst.size_of());
+    assert_eq!(b.to_string(), "memcpy(&dst, src, sizeof(dst));\n");
+    assert_eq!(b.required_includes(Dialect::C), vec!["string.h"]);
+    assert_eq!(b.required_includes(Dialect::Cpp), vec!["cstring"]);
+
+    let mut b = Block::new();
+    b.memset(src, Expr::new_num(0), Expr::new_num(16));
+    assert_eq!(b.to_string(), "memset(src, 0x0, 0x10);\n");
+}
+
+#[test]
+fn test_block_bit_cast() {
+    let mut b = Block::new();
+    let bits = Expr::new_var("bits", Type::new_uint32());
+    let x = Expr::new_var("x", Type::new(BaseType::Float));
+    b.bit_cast(bits, x);
+    assert_eq!(b.to_string(), "memcpy(&bits, &x, sizeof(bits));\n");
+    assert_eq!(b.required_includes(Dialect::C), ["string.h"]);
+}
+
+#[test]
+fn test_block_switch_char_labels() {
+    let c = Expr::new_var("c", Type::new_char());
+    let mut b = Block::new();
+    let s = b.new_switch(&c);
+    s.new_case(Expr::new_char('a')).return_expr(Expr::new_num(1));
+    s.new_case(Expr::new_char('\'')).return_expr(Expr::new_num(2));
+    s.new_case(Expr::new_char('\n')).return_expr(Expr::new_num(3));
+
+    let out = b.to_string();
+    assert!(out.contains("case 'a':"));
+    assert!(out.contains("case '\\'':"));
+    assert!(out.contains("case '\\n':"));
+}
+
+#[test]
+fn test_block_switch_cast_labels() {
+    let red = Expr::new_num(1).cast_to(Type::new_enum("color"));
+    let small = Expr::new_num(2).cast_to(Type::new_typedef("small_t"));
+    assert!(red.is_integral_const());
+    assert!(small.is_integral_const());
+    assert!(!Expr::new_str("foo").is_integral_const());
+
+    let c = Expr::new_var("c", Type::new_enum("color"));
+    let mut b = Block::new();
+    let s = b.new_switch(&c);
+    s.new_case(red).return_expr(Expr::new_num(1));
+    s.new_case(small).return_expr(Expr::new_num(2));
+
+    let out = b.to_string();
+    assert!(out.contains("case (enum color)(0x1):"));
+    assert!(out.contains("case (small_t)(0x2):"));
+}
+
+#[test]
+#[should_panic(expected = "invalid case label")]
+fn test_block_switch_string_label() {
+    let c = Expr::new_var("c", Type::new_cstr());
+    let mut b = Block::new();
+    b.new_switch(&c).new_case(Expr::new_str("foo"));
+}
+
+#[test]
+#[should_panic(expected = "invalid case label")]
+fn test_block_switch_float_label() {
+    let c = Expr::new_var("c", Type::new(BaseType::Double));
+    let mut b = Block::new();
+    b.new_switch(&c).new_case(Expr::new_num(1).cast_to(Type::new(BaseType::Double)));
+}
+
+#[test]
+fn test_block_section() {
+    let mut b = Block::new();
+    b.fn_call("setup", vec![]);
+    b.section("cleanup");
+    b.fn_call("teardown", vec![]);
+
+    let sep = "/".repeat(100);
+    assert_eq!(b.to_string(), format!("setup();\n{sep}\n// cleanup\n{sep}\nteardown();\n"));
+}
+
+#[test]
+fn test_block_compound_assign() {
+    let mut b = Block::new();
+    b.compound_assign(
+        Expr::new_var("sum", Type::new_int32()),
+        "+",
+        Expr::new_var("x", Type::new_int32()),
+    )
+    .compound_assign(
+        Expr::new_var("mask", Type::new_uint32()),
+        "<<=",
+        Expr::new_var("n", Type::new_uint32()),
+    )
+    .increment(Expr::new_var("i", Type::new_int32()))
+    .decrement(Expr::new_var("j", Type::new_int32()));
+    assert_eq!(b.to_string(), "sum += x;\nmask <<= n;\ni++;\nj--;\n");
+}
+
+#[test]
+fn test_block_local() {
+    let mut b = Block::new();
+    let x = b.local("x", Type::new_int32());
+    let sum = b.local_init("sum", Type::new_int32(), Expr::new_num(0));
+    b.assign(x.clone(), Expr::new_num(1));
+    b.compound_assign(sum, "+", x);
+    assert_eq!(b.to_string(), "int32_t x;\nint32_t sum = 0x0;\nx = 0x1;\nsum += x;\n");
+}
+
+#[test]
+fn test_block_switch_case_range() {
+    let c = Expr::new_var("c", Type::new_int32());
+    let mut b = Block::new();
+    let s = b.new_switch(&c);
+    s.new_case_range(Expr::new_num(1), Expr::new_num(3))
+        .return_expr(Expr::new_num(1));
+    s.new_case(Expr::new_num(6)).return_expr(Expr::new_num(2));
+
+    // without the GNU extensions, the range is expanded
+    let out = b.to_string();
+    assert!(out.contains("case 0x1:\ncase 0x2:\ncase 0x3:\n"));
+    assert!(out.contains("case 0x6:\n"));
+
+    let mut s = Scope::new();
+    s.set_gnu_extensions().set_filename("range.c");
+    s.new_function("classify", Type::new_int32()).body().merge(b);
+    let out = s.to_string_with_defs();
+    assert!(out.contains("case 0x1 ... 0x3:\n"));
+    assert!(out.contains("case 0x6:\n"));
+}
+
+#[test]
+fn test_block_return_cast() {
+    let len = Expr::new_var("len", Type::new_size());
+    let mut b = Block::new();
+    b.return_cast(len.clone(), Type::new_int32());
+    assert_eq!(b.to_string(), "return (int32_t)(len);\n");
+
+    let mut ret_ty = Type::new_int32();
+    ret_ty.set_value_const();
+
+    let mut b = Block::new();
+    b.return_checked(len, &ret_ty);
+    b.return_checked(Expr::new_var("x", Type::new_int32()), &Type::new_int32());
+    b.return_checked(Expr::new_num(0), &Type::new_int32());
+    assert_eq!(b.to_string(), "return (int32_t)(len);\nreturn x;\nreturn 0x0;\n");
+
+    // the top-level qualifiers do not require a cast
+    let mut name_ty = Type::new_char();
+    name_ty.set_value_const().pointer();
+    let mut b = Block::new();
+    b.return_checked(Expr::new_var("x", Type::new_int32()), &ret_ty);
+    b.return_checked(Expr::new_var("name", name_ty.clone()), name_ty.clone().constant());
+    assert_eq!(b.to_string(), "return x;\nreturn name;\n");
+}
+
+#[test]
+fn test_block_check_declarations() {
+    let mut b = Block::new();
+    b.new_variable("i", Type::new_int32());
+    b.new_variable("j", Type::new_int32());
+    b.new_variable("i", Type::new_uint32());
+    b.new_variable("i", Type::new_uint32());
+    assert_eq!(b.check_declarations(), vec![String::from("i")]);
+
+    // a nested block is a separate scope
+    let mut b = Block::new();
+    b.new_variable("tmp", Type::new_int32());
+    let then = b.new_ifelse(&Expr::new_var("c", Type::new_bool())).then_branch();
+    then.new_variable("tmp", Type::new_int32());
+    then.new_variable("x", Type::new_int32());
+    then.new_variable("x", Type::new_int32());
+    assert_eq!(b.check_declarations(), vec![String::from("x")]);
+}
+
+#[test]
+fn test_block_raw_no_semi() {
+    let mut b = Block::new();
+    let body = b.new_ifelse(&Expr::new_var("c", Type::new_bool())).then_branch();
+    body.raw_no_semi("#pragma unroll");
+    body.raw_no_semi("{\n    int tmp = 0;\n    use(tmp);\n}");
+    body.raw_str("done()");
+    assert_eq!(
+        b.to_string(),
+        "if (c) {\n    #pragma unroll\n    {\n        int tmp = 0;\n        use(tmp);\n    }\n    done();\n}\n"
+    );
+}
+
+#[test]
+fn test_block_break_to_label() {
+    let i = Expr::new_var("i", Type::new_int32());
+    let j = Expr::new_var("j", Type::new_int32());
+    let found = Expr::fn_call("found", vec![i.clone(), j.clone()]);
+
+    let mut b = Block::new();
+    b.new_for_loop_with_exit(
+        "search_done",
+        &Expr::binop(i.clone(), "=", Expr::new_num(0)),
+        &Expr::binop(i.clone(), "<", Expr::new_num(4)),
+        &Expr::uop("++", i),
+    )
+    .body()
+    .new_while_loop(&Expr::binop(j.clone(), "<", Expr::new_num(4)))
+    .body()
+    .new_ifelse(&found)
+    .then_branch()
+    .break_to_label("search_done");
+    b.return_none();
+
+    assert_eq!(
+        b.to_string(),
+        "for ((i = 0x0); (i < 0x4); ++(i)) \n{\n    while ((j < 0x4)) \n    {\n        \
+         if (found(i, j)) {\n            goto search_done;\n        }\n    }\n}\n\
+         search_done:\n;\nreturn;\n"
+    );
+}
+
+#[test]
+fn test_block_alloc_free() {
+    let p = Expr::new_var("p", Type::new_uint32().to_ptr());
+    let n = Expr::new_var("n", Type::new_size());
+
+    let mut b = Block::new();
+    b.alloc(p.clone(), n, Type::new_uint32());
+    assert_eq!(b.to_string(), "p = (uint32_t *)(malloc((n * sizeof(uint32_t))));\n");
+    assert_eq!(b.required_includes(Dialect::C), vec!["stdlib.h"]);
+
+    let mut b = Block::new();
+    b.free(p);
+    assert_eq!(b.to_string(), "free(p);\n");
+    assert_eq!(b.required_includes(Dialect::C), vec!["stdlib.h"]);
+
+    // calls are found anywhere in the statements and conditions
+    let size = Expr::new_num(16);
+    let buf = Expr::fn_call("calloc", vec![size.clone(), Expr::new_num(1)]);
+    let mut b = Block::new();
+    b.new_variable("buf", Type::new_void().to_ptr()).set_value(buf);
+    assert_eq!(b.required_includes(Dialect::C), vec!["stdlib.h"]);
+    assert_eq!(b.required_includes(Dialect::Cpp), vec!["cstdlib"]);
+
+    let dst = Expr::new_var("dst", Type::new_void().to_ptr());
+    let cmp = Expr::fn_call("memcmp", vec![dst.clone(), dst, size]);
+    let mut b = Block::new();
+    b.new_ifelse(&cmp).then_branch().return_none();
+    assert_eq!(b.required_includes(Dialect::C), vec!["string.h"]);
+}
@@ -41,3 +41,368 @@ fn test_class_inheritance() {
     s.set_base("Foo", Visibility::Public);
     assert_eq!(s.to_string(), "class MyClass : public Foo { };\n");
 }
+
+#[test]
+fn test_class_accessor_pair() {
+    let mut s = Class::new("MyVec");
+    s.new_accessor_pair("at", Type::new_int32());
+    let decl = s.to_string();
+    assert!(decl.contains("int32_t & at(size_t idx);"));
+    assert!(decl.contains("const int32_t & at(size_t idx) const;"));
+}
+
+#[test]
+fn test_class_virtual_inheritance() {
+    let mut s = Class::new("MyClass");
+    s.push_virtual_base("Foo", Visibility::Public);
+    s.push_base("Bar", Visibility::Protected);
+    assert_eq!(s.to_string(), "class MyClass : public virtual Foo, protected Bar { };\n");
+}
+
+#[test]
+fn test_class_move_constructor() {
+    let mut s = Class::new("Foo");
+    s.new_constructor().set_move(true);
+    let decl = s.to_string();
+    assert!(decl.contains("Foo(Foo && other);"));
+    assert!(!decl.contains("& &"));
+}
+
+#[test]
+fn test_class_method_calling_convention() {
+    let mut s = Class::new("Foo");
+    s.new_method("bar", Type::new_void())
+        .set_public()
+        .calling_convention("__fastcall");
+    assert!(s.to_string().contains("void __fastcall bar(void);"));
+}
+
+#[test]
+fn test_class_display_decl_only() {
+    let mut c = Class::new("Foo");
+    c.push_doc_str("the foo class");
+    c.new_method("bar", Type::new_void()).set_public().body().return_none();
+    let out = format!("{c}");
+    assert_eq!(out.matches("class Foo").count(), 1);
+    assert_eq!(out.matches("the foo class").count(), 1);
+    assert!(!out.contains("Foo::bar"));
+}
+
+#[test]
+fn test_class_method_out_of_line_def() {
+    let mut s = Scope::new();
+    s.set_filename("foo.cpp");
+    s.new_class("Foo")
+        .new_method("size", Type::new_size())
+        .set_public()
+        .set_virtual()
+        .set_const()
+        .set_override()
+        .body()
+        .return_expr(Expr::new_num(0));
+
+    let out = s.to_string();
+    assert!(out.contains("virtual size_t size(void) const override;"));
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("size_t Foo::size(void) const {\n    return 0x0;\n}"));
+    assert!(!out.contains("virtual"));
+    assert!(!out.contains("override"));
+}
+
+#[test]
+fn test_class_method_noreturn() {
+    let mut s = Scope::new();
+    s.set_filename("foo.cpp");
+    s.set_dialect(Dialect::Cpp);
+    s.new_class("Foo")
+        .new_method("fail", Type::new_void())
+        .set_public()
+        .set_noreturn()
+        .body()
+        .fn_call("abort", vec![]);
+
+    let out = s.to_string();
+    assert!(out.contains("[[noreturn]] void fail(void);"));
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("void Foo::fail(void) {\n    abort();\n}"));
+    assert!(!out.contains("[[noreturn]] void Foo::fail"));
+}
+
+#[test]
+fn test_class_rule_of_five() {
+    let mut c = Class::new("Buf");
+    c.declare_rule_of_five(false);
+    let out = c.to_string();
+    assert!(out.contains("~Buf(void);"));
+    assert!(out.contains("Buf(Buf const & other) = delete;"));
+    assert!(out.contains("Buf(Buf && other) = delete;"));
+    assert!(out.contains("Buf & operator=(Buf const & other) = delete;"));
+    assert!(out.contains("Buf & operator=(Buf && other) = delete;"));
+
+    let mut c = Class::new("Buf");
+    c.declare_rule_of_five(true);
+    let out = c.to_string();
+    assert!(out.contains("~Buf(void) = default;"));
+    assert!(out.contains("Buf(Buf const & other) = default;"));
+    assert!(out.contains("Buf(Buf && other) = default;"));
+    assert!(out.contains("Buf & operator=(Buf const & other) = default;"));
+    assert!(out.contains("Buf & operator=(Buf && other) = default;"));
+}
+
+#[test]
+fn test_class_constructor_specifiers() {
+    let mut c = Class::new("Foo");
+    let ctor = c.new_constructor();
+    ctor.new_param("x", Type::new_int32());
+    ctor.explicit()
+        .constexpr()
+        .noexcept()
+        .inside_def()
+        .push_initializer("x_", Expr::new_var("x", Type::new_int32()));
+    ctor.body().return_none();
+    assert!(c.to_string().contains(
+        "    explicit constexpr Foo(int32_t x) noexcept\n        : x_(x)\n    {\n        return;\n    }\n"
+    ));
+
+    // `explicit` does not appear on an out-of-line definition
+    let mut s = Scope::new();
+    s.set_filename("ctor.cpp");
+    let ctor = s.new_class("Foo").new_constructor();
+    ctor.new_param("x", Type::new_int32());
+    ctor.explicit().constexpr().noexcept();
+    ctor.body().return_none();
+    assert!(s.to_string().contains("explicit constexpr Foo(int32_t x) noexcept;"));
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("constexpr Foo::Foo(int32_t x) noexcept\n"));
+    assert!(!out.contains("explicit"));
+}
+
+#[test]
+fn test_class_nodiscard() {
+    let mut c = Class::new("Error");
+    c.set_nodiscard();
+    assert!(c.to_string().contains("class [[nodiscard]] Error {"));
+}
+
+#[test]
+fn test_class_method_declspec() {
+    let mut s = Scope::new();
+    s.set_filename("engine.cpp").set_dialect(Dialect::Msvc);
+    s.new_class("Engine")
+        .new_method("run", Type::new_void())
+        .set_public()
+        .declspec("noinline")
+        .body()
+        .return_none();
+    assert!(s.to_string().contains("__declspec(noinline) void run(void);"));
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("void Engine::run(void) {"));
+    assert!(!out.contains("__declspec"));
+
+    s.set_dialect(Dialect::Cpp);
+    assert!(s.to_string().contains("__attribute__((noinline)) void run(void);"));
+}
+
+#[test]
+fn test_class_conversion_operator() {
+    let mut s = Scope::new();
+    s.set_filename("handle.cpp");
+    let c = s.new_class("Handle");
+    let mut m = Method::new_conversion(Type::new_bool());
+    m.set_public()
+        .body()
+        .return_expr(Expr::not_null(Expr::new_var("ptr", Type::new_void().to_ptr())));
+    c.push_method(m);
+    assert!(s.to_string().contains("    operator bool() const;\n"));
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("\nHandle::operator bool() const {\n"));
+}
+
+#[test]
+fn test_class_delete_reason() {
+    let mut s = Scope::new();
+    let c = s.new_class("Token");
+    c.new_constructor().set_delete_reason("use Token::parse");
+    c.new_method("clone", Type::new_class("Token"))
+        .set_public()
+        .set_delete_reason("tokens are unique");
+    c.new_method("reset", Type::new_void()).set_public().set_delete();
+    c.new_method("swap", Type::new_void())
+        .set_public()
+        .set_delete_reason("use \"std::swap\"");
+
+    s.set_dialect(Dialect::Cpp);
+    s.set_delete_reasons();
+    let out = s.to_string();
+    assert!(out.contains("Token(void) = delete(\"use Token::parse\");"));
+    assert!(out.contains("Token clone(void) = delete(\"tokens are unique\");"));
+    assert!(out.contains("void reset(void) = delete;"));
+    assert!(out.contains("void swap(void) = delete(\"use \\\"std::swap\\\"\");"));
+
+    s.toggle_delete_reasons(false);
+    let out = s.to_string();
+    assert!(out.contains("Token(void) = delete;"));
+    assert!(out.contains("Token clone(void) = delete;"));
+}
+
+#[test]
+fn test_class_method_body_comment() {
+    // method bodies are blocks, which hold comments between statements
+    let mut c = Class::new("Counter");
+    c.new_method("reset", Type::new_void())
+        .set_public()
+        .set_inside_def()
+        .body()
+        .new_comment("start counting from scratch")
+        .assign(Expr::new_var("count", Type::new_uint32()), Expr::new_num(0));
+    assert!(c.to_string().contains(
+        "void reset(void) {\n        // start counting from scratch\n        count = 0x0;\n    }"
+    ));
+}
+
+#[test]
+fn test_class_rename() {
+    let mut c = Class::new("Foo");
+    c.new_constructor().push_param(MethodParam::new("x", Type::new_int32()));
+    c.new_destructor();
+    assert_eq!(c.name(), "Foo");
+
+    c.set_name("Bar");
+    assert_eq!(c.name(), "Bar");
+    assert_eq!(c.to_type().to_string(), "Bar");
+
+    let out = c.to_string();
+    assert!(out.starts_with("class Bar {"));
+    assert!(out.contains("Bar(int32_t x);"));
+    assert!(out.contains("~Bar(void);"));
+    assert!(!out.contains("Foo"));
+}
+
+#[test]
+fn test_class_rename_rule_of_five() {
+    let mut c = Class::new("Foo");
+    c.declare_rule_of_five(false);
+    c.set_name("Bar");
+
+    let out = c.to_string();
+    assert!(out.contains("Bar(Bar const & other) = delete;"));
+    assert!(out.contains("Bar(Bar && other) = delete;"));
+    assert!(out.contains("Bar & operator=(Bar const & other) = delete;"));
+    assert!(out.contains("Bar & operator=(Bar && other) = delete;"));
+    assert!(!out.contains("Foo"));
+}
+
+#[test]
+fn test_class_method_for_loop_comment() {
+    // the body of a for loop is a full block, including comments and gotos
+    let i = Expr::new_var("i", Type::new_int32());
+    let mut c = Class::new("Counter");
+    let l = c
+        .new_method("reset", Type::new_void())
+        .set_public()
+        .set_inside_def()
+        .body()
+        .new_for_loop(
+            &Expr::binop(i.clone(), "=", Expr::new_num(0)),
+            &Expr::binop(i.clone(), "<", Expr::new_num(4)),
+            &Expr::uop("++", i),
+        );
+    l.body().new_comment("clear the slot").goto("done");
+
+    assert!(c.to_string().contains(
+        "for ((i = 0x0); (i < 0x4); ++(i)) \n        {\n            // clear the slot\n            goto done;\n        }\n"
+    ));
+}
+
+#[test]
+fn test_class_remove_members() {
+    let mut c = Class::new("Reg");
+    c.new_attribute("val", Type::new_uint32());
+    c.new_attribute("addr", Type::new_uintptr());
+    c.new_method("get", Type::new_uint32()).set_public();
+    c.new_method("set", Type::new_void()).set_public();
+    c.new_method("set", Type::new_void())
+        .set_public()
+        .new_param("v", Type::new_uint32());
+
+    // remove a single overload by signature
+    let m = c.remove_method_by_signature("set", &[Type::new_uint32()]).unwrap();
+    assert_eq!(m.name(), "set");
+    assert!(c.remove_method_by_signature("set", &[Type::new_uint64()]).is_none());
+    let out = c.to_string();
+    assert!(out.contains("void set(void);"));
+    assert!(!out.contains("void set(uint32_t v);"));
+
+    // remove the remaining method by name
+    assert_eq!(c.remove_method("set").len(), 1);
+    assert!(c.method_by_name("set").is_none());
+    assert!(c.method_by_name("get").is_some());
+
+    assert!(c.remove_attribute("addr").is_some());
+    assert!(c.remove_attribute("addr").is_none());
+    assert!(!c.to_string().contains("addr"));
+}
+
+#[test]
+fn test_class_replace_method() {
+    let mut c = Class::new("Reg");
+    c.new_method("get", Type::new_uint32()).set_public();
+    c.new_method("reset", Type::new_void()).set_public();
+
+    let mut m = Method::new("get", Type::new_uint64());
+    m.set_public().set_const();
+    assert!(c.replace_method("get", m).is_some());
+
+    let out = c.to_string();
+    assert!(out.contains("uint64_t get(void) const;\n    void reset(void);"));
+    assert!(!out.contains("uint32_t get"));
+}
+
+#[test]
+fn test_class_method_specialization() {
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp).set_filename("spec.cpp");
+    let c = s.new_class("Foo");
+    c.new_method("bar", Type::new_void())
+        .set_public()
+        .set_specialization(vec![TemplateArg::Type(Type::new_plain_int())])
+        .body()
+        .fn_call("run", vec![]);
+    c.new_constructor()
+        .set_specialization(vec![TemplateArg::new_num(4)])
+        .body()
+        .fn_call("init", vec![]);
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("template <>\nvoid Foo<int>::bar(void) {\n    run();\n}\n"));
+    assert!(out.contains("template <>\nFoo<4>::Foo(void)"));
+}
+
+#[test]
+fn test_method_to_c_function() {
+    let foo = Type::new_struct("Foo");
+
+    let mut m = Method::new("get", Type::new_int32());
+    m.set_const().new_param("idx", Type::new_size());
+    m.body().return_expr(Expr::new_num(0));
+    assert_eq!(
+        m.to_c_function(&foo).to_string(),
+        "int32_t get(const struct Foo * self, size_t idx) {\n    return 0x0;\n}\n"
+    );
+
+    let mut m = Method::new("reset", Type::new_void());
+    m.new_param("val", Type::new_int32());
+    assert_eq!(
+        m.to_c_function(&foo).to_string(),
+        "void reset(struct Foo * self, int32_t val);\n"
+    );
+
+    let mut m = Method::new("create", foo.to_ptr());
+    m.set_static();
+    assert_eq!(m.to_c_function(&foo).to_string(), "struct Foo * create(void);\n");
+}
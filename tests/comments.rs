@@ -0,0 +1,65 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2021, 2022 Reto Achermann (The University of British Columbia)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Comment Tests
+//!
+//! This module exercises the comment tests
+
+use crustal::*;
+
+#[test]
+fn test_comment_block_escapes_terminator() {
+    let mut c = Comment::with_str("matches a/*/b */ here");
+    c.set_block();
+    assert_eq!(c.to_string(), "/* matches a/* /b * / here */\n");
+
+    let mut c = Comment::with_str("first */\nsecond");
+    c.set_block();
+    assert_eq!(c.to_string(), "/*\n * first * /\n * second\n */\n");
+
+    let mut s = Scope::new();
+    s.new_comment("end */ of comment").set_block();
+    assert_eq!(s.to_string(), "\n\n/* end * / of comment */");
+}
+
+#[test]
+fn test_comment_heading_width() {
+    let sep = "/".repeat(100);
+    assert_eq!(Comment::new_heading("Types").to_string(), format!("{sep}\n// Types\n{sep}\n"));
+
+    let mut s = Scope::new();
+    s.set_line_width(80);
+    s.push_comment(Comment::new_heading("Types"));
+    let sep = "/".repeat(80);
+    assert_eq!(s.to_string(), format!("\n\n{sep}\n// Types\n{sep}"));
+
+    // headings inside indented code end at the column limit
+    let mut s = Scope::new();
+    s.set_line_width(80).set_filename("heading.c");
+    s.new_function("run", Type::new_void()).body().section("setup");
+    let out = s.to_string_with_defs();
+    let sep = format!("    {}", "/".repeat(76));
+    assert!(out.contains(&format!("{{\n{sep}\n    // setup\n{sep}\n}}")));
+}
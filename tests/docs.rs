@@ -0,0 +1,49 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2021, 2022 Reto Achermann (The University of British Columbia)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Documentation Tests
+//!
+//! This module exercises the documentation tests
+
+use crustal::*;
+
+#[test]
+fn test_doc_code_block() {
+    let long = "x".repeat(60);
+    let code = format!("```\nint main(void) {{\n    call_something_long({long}, {long});\n\n    return 0;\n}}\n```");
+
+    let mut d = Doc::with_str("Example usage:");
+    d.add_text(&code);
+    d.add_code_block("foo(bar);");
+
+    let mut v = Variable::new("x", Type::new_int32());
+    v.doc(d);
+    assert_eq!(
+        v.to_string(),
+        format!(
+            "/// Example usage:\n/// ```\n/// int main(void) {{\n///     call_something_long({long}, {long});\n/// \n///     return 0;\n/// }}\n/// ```\n/// ```\n/// foo(bar);\n/// ```\nint32_t x;\n"
+        )
+    );
+}
@@ -0,0 +1,168 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2021, 2022 Reto Achermann (The University of British Columbia)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Enum Tests
+//!
+//! This module exercises the enum tests
+
+use crustal::*;
+
+#[test]
+fn test_enum_trailing_doc() {
+    let mut e = Enum::new("color");
+    e.new_variant("RED").doc_str("the color red").set_trailing_doc();
+    e.new_variant("GREEN").doc_str("the color green").set_trailing_doc();
+    e.new_variant("BLUE");
+
+    let mut scope = Scope::new();
+    scope.push_enum(e);
+    assert!(scope.to_string().contains(
+        "enum color {
+    RED,   ///< the color red
+    GREEN, ///< the color green
+    BLUE
+};"
+    ));
+}
+
+#[test]
+fn test_enum_to_string_function() {
+    let mut e = Enum::new("color");
+    e.new_variant("RED");
+    e.new_variant("GREEN");
+
+    let mut scope = Scope::new();
+    scope.push_function(e.to_string_function());
+    scope.set_filename("color.c");
+
+    let out = scope.to_string_with_defs();
+    assert!(out.contains("const char * color_to_string(enum color val) {"));
+    assert!(out.contains("case RED:\n    {\n        return \"RED\";\n    }"));
+    assert!(out.contains("case GREEN:\n    {\n        return \"GREEN\";\n    }"));
+    assert!(out.contains("default: \n    {\n        return \"unknown\";\n    }"));
+}
+
+#[test]
+fn test_enum_auto_sequential() {
+    let mut e = Enum::new("state");
+    e.new_variant("IDLE");
+    e.new_variant("RUN");
+    e.new_variant("ERR").set_value(10);
+    e.new_variant("DEAD");
+    assert!(e.auto_sequential().is_some());
+
+    let values: Vec<Option<u64>> = (0..4).map(|i| e.variant_by_idx(i).unwrap().value()).collect();
+    assert_eq!(values, vec![Some(0), Some(1), Some(10), Some(11)]);
+}
+
+#[test]
+fn test_enum_auto_values_overflow() {
+    let mut e = Enum::new("state");
+    e.new_variant("IDLE");
+    e.new_variant("LAST").set_value(u64::MAX);
+    e.new_variant("NEXT");
+    assert!(e.auto_sequential().is_none());
+    assert_eq!(e.variant_by_idx(0).unwrap().value(), None);
+
+    let mut e = Enum::new("perm");
+    e.new_variant("HIGH").set_value(1 << 63);
+    assert!(e.auto_flags().is_some());
+    e.new_variant("NEXT");
+    assert!(e.auto_flags().is_none());
+    assert_eq!(e.variant_by_idx(1).unwrap().value(), None);
+}
+
+#[test]
+fn test_enum_auto_flags() {
+    let mut e = Enum::new("perm");
+    e.new_variant("A");
+    e.new_variant("B");
+    e.new_variant("C");
+    e.new_variant("E").set_value(16);
+    e.new_variant("F");
+    assert!(e.auto_flags().is_some());
+
+    let values: Vec<Option<u64>> = (0..5).map(|i| e.variant_by_idx(i).unwrap().value()).collect();
+    assert_eq!(values, vec![Some(1), Some(2), Some(4), Some(16), Some(32)]);
+
+    let mut scope = Scope::new();
+    scope.push_enum(e);
+    assert!(scope.to_string().contains("    A = 1,\n    B = 2,\n    C = 4,\n"));
+}
+
+#[test]
+fn test_enum_prefix_variants() {
+    let mut e = Enum::new("color");
+    e.new_variant("red");
+    e.new_variant("green");
+    e.new_variant("blue");
+    e.map_variant_names(str::to_uppercase).prefix_variants("COLOR_");
+
+    let mut scope = Scope::new();
+    scope.push_enum(e);
+    assert!(scope
+        .to_string()
+        .contains("enum color {\n    COLOR_RED,\n    COLOR_GREEN,\n    COLOR_BLUE\n};"));
+}
+
+#[test]
+fn test_enum_rename() {
+    let mut e = Enum::new("color");
+    e.new_variant("RED");
+    e.set_name("colour");
+    assert_eq!(e.name(), "colour");
+    assert_eq!(e.to_type().to_string(), "enum colour");
+}
+
+#[test]
+fn test_enum_switch_with_handlers() {
+    let mut e = Enum::new("op");
+    e.new_variant("OP_ADD");
+    e.new_variant("OP_SUB");
+    e.new_variant("OP_NOP");
+
+    let a = Expr::new_var("a", Type::new_int32());
+    let b = Expr::new_var("b", Type::new_int32());
+    let s = Switch::from_enum_with(&Expr::new_var("op", e.to_type()), &e, |v| {
+        let mut body = Block::new();
+        match v.name() {
+            "OP_ADD" => body.return_expr(Expr::binop(a.clone(), "+", b.clone())),
+            "OP_SUB" => body.return_expr(Expr::binop(a.clone(), "-", b.clone())),
+            _ => body.new_comment("nothing to do").break_stmt(),
+        };
+        body
+    });
+
+    let mut blk = Block::new();
+    blk.switch(s);
+    let out = blk.to_string();
+    assert!(out.contains("case OP_ADD:"));
+    assert!(out.contains("return (a + b);"));
+    assert!(out.contains("case OP_SUB:"));
+    assert!(out.contains("return (a - b);"));
+    assert!(out.contains("case OP_NOP:"));
+    assert!(out.contains("// nothing to do"));
+    assert!(!out.contains("default:"));
+}
@@ -0,0 +1,272 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2021, 2022 Reto Achermann (The University of British Columbia)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Expression Tests
+//!
+//! This module exercises the expression tests
+
+use crustal::*;
+
+#[test]
+fn test_expr_string_concat() {
+    let e = Expr::string_concat(&["error: ", "value \"x\" out of range\n"]);
+    assert_eq!(e.to_string(), "\"error: \" \"value \\\"x\\\" out of range\\n\"");
+}
+
+#[test]
+fn test_expr_compound_literal() {
+    let point = Expr::compound_literal(
+        Type::new_struct("point"),
+        Expr::designated_init(vec![("x", Expr::new_num(1)), ("y", Expr::new_num(2))]),
+    );
+
+    let mut b = Block::new();
+    b.fn_call("draw", vec![point.clone()]);
+    b.return_expr(point);
+    assert_eq!(
+        b.to_string(),
+        "draw((struct point){.x = 0x1, .y = 0x2});\nreturn (struct point){.x = 0x1, .y = 0x2};\n"
+    );
+}
+
+#[test]
+fn test_expr_typed_num() {
+    let e = Expr::new_typed_num(1, Type::new_enum("color"));
+    assert_eq!(e.to_string(), "(enum color)0x1");
+
+    let e = Expr::new_typed_num(1, Type::new_uint8());
+    assert_eq!(e.to_string(), "(uint8_t)0x1");
+
+    let e = Expr::new_typed_num(1, Type::new_uint32());
+    assert_eq!(e.to_string(), "0x1u");
+
+    let e = Expr::new_typed_num(1, Type::new_uint64());
+    assert_eq!(e.to_string(), "UINT64_C(0x1)");
+
+    let e = Expr::new_typed_num(0x7fff_ffff_ffff, Type::new_int64());
+    assert_eq!(e.to_string(), "INT64_C(0x7fffffffffff)");
+
+    // the constant macros are defined by `stdint.h`
+    let mut b = Block::new();
+    b.return_expr(e);
+    assert_eq!(b.required_includes(Dialect::C), ["stdint.h"]);
+    assert_eq!(b.required_includes(Dialect::Cpp), ["cstdint"]);
+}
+
+#[test]
+fn test_expr_deref_addr_of() {
+    let p = Expr::new_var("p", Type::new_int32().to_ptr());
+    assert_eq!(p.deref().to_string(), "*p");
+    assert_eq!(p.addr_of().to_string(), "&p");
+
+    let pp = Expr::new_var("pp", Type::new_int32().to_ptr().to_ptr());
+    assert_eq!(pp.deref().deref().to_string(), "**pp");
+
+    let e = Expr::binop(p, "+", Expr::new_num(1));
+    assert_eq!(e.deref().to_string(), "*(p + 0x1)");
+}
+
+#[test]
+fn test_expr_null_checks() {
+    let p = Expr::new_var("p", Type::new_int32().to_ptr());
+    assert_eq!(Expr::is_null(p.clone()).to_string(), "(p == NULL)");
+    assert_eq!(Expr::not_null(p.clone()).to_string(), "(p != NULL)");
+
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp).set_filename("null.cpp");
+    s.new_function("guard", Type::new_void())
+        .body()
+        .new_ifelse(&Expr::not_null(p))
+        .then_branch()
+        .return_none();
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("if ((p != nullptr)) {"));
+}
+
+#[test]
+fn test_expr_nested_null_cpp() {
+    let p = Expr::new_var("p", Type::new_int32().to_ptr());
+    let table = Expr::new_var("table", Type::new_int32().to_array(2));
+
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp).set_filename("null.cpp");
+    let body = s.new_function("reset", Type::new_void()).body();
+    body.assign(p.clone(), Expr::null().cast_to(Type::new_int32().to_ptr()));
+    body.assign(Expr::array_access(&table, &Expr::not_null(p)), Expr::new_num(0));
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("p = (int32_t *)(nullptr);"));
+    assert!(out.contains("table[(p != nullptr)] = 0x0;"));
+    assert!(!out.contains("NULL"));
+
+    let null = Expr::null().cast_to(Type::new_void().to_ptr());
+    assert_eq!(null.to_string(), "(void *)(NULL)");
+}
+
+#[test]
+fn test_expr_bit_helpers() {
+    let reg = Expr::new_var("reg", Type::new_uint32());
+    let n = Expr::new_var("n", Type::new_uint32());
+    assert_eq!(Expr::set_bit(reg.clone(), n.clone()).to_string(), "(reg |= (0x1u << n))");
+    assert_eq!(Expr::clear_bit(reg.clone(), n.clone()).to_string(), "(reg &= ~((0x1u << n)))");
+    assert_eq!(Expr::test_bit(reg, Expr::new_num(3)).to_string(), "((reg >> 0x3) & 0x1)");
+
+    // the mask of a high bit of a 64-bit register does not overflow an int
+    let reg = Expr::new_var("reg", Type::new_uint64());
+    let mask = Expr::set_bit(reg.clone(), Expr::new_num(63)).to_string();
+    assert_eq!(mask, "(reg |= (UINT64_C(0x1) << 0x3f))");
+    let mask = Expr::clear_bit(Expr::Raw(String::from("REG")), Expr::new_num(40)).to_string();
+    assert_eq!(mask, "(REG &= ~((UINT64_C(0x1) << 0x28)))");
+}
+
+#[test]
+fn test_expr_try_deref() {
+    let p = Expr::new_var("p", Type::new_int32().to_ptr());
+    assert_eq!(p.try_deref().unwrap().to_string(), "*p");
+
+    let i = Expr::new_var("i", Type::new_int32());
+    assert!(i.try_deref().is_none());
+    assert!(Expr::new_num(5).try_deref().is_none());
+}
+
+#[test]
+fn test_expr_member_ptr_call() {
+    let obj = Expr::new_var("obj", Type::new_class("Foo"));
+    let p = Expr::new_var("p", Type::new_class("Foo").to_ptr());
+    let mptr = Expr::new_var("handler", Type::new_class("Handler"));
+    let x = Expr::new_var("x", Type::new_int32());
+
+    let e = Expr::member_ptr_call(&obj, &mptr, vec![x.clone()]);
+    assert_eq!(e.to_string(), "((obj).*(handler))(x)");
+
+    let e = Expr::member_ptr_call(&p, &mptr, vec![x]);
+    assert_eq!(e.to_string(), "((p)->*(handler))(x)");
+}
+
+#[test]
+fn test_expr_bit_cast() {
+    let x = Expr::new_var("x", Type::new(BaseType::Float));
+    let e = Expr::bit_cast(&x, Type::new_uint32());
+
+    let mut s = Scope::new();
+    s.set_gnu_extensions().set_filename("bits.c");
+    let f = s.new_function("to_bits", Type::new_uint32());
+    f.body().return_expr(e.clone());
+    assert_eq!(f.body().required_includes(Dialect::C), ["string.h"]);
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains(
+        "return ({ __typeof__(x) bit_cast_src = (x); uint32_t bit_cast_dst; \
+         memcpy(&bit_cast_dst, &bit_cast_src, sizeof(bit_cast_dst)); bit_cast_dst; });"
+    ));
+
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp).set_filename("bits.cpp");
+    let body = s.new_function("to_bits", Type::new_uint32()).body();
+    body.return_expr(e.cast_to(Type::new_uint64()));
+    assert_eq!(body.required_includes(Dialect::Cpp), ["bit"]);
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("return (uint64_t)(std::bit_cast<uint32_t>(x));"));
+}
+
+#[test]
+fn test_expr_bit_cast_without_gnu() {
+    let x = Expr::new_var("x", Type::new(BaseType::Float));
+    let e = Expr::bit_cast(&x, Type::new_uint32());
+    assert_eq!(
+        e.to_string(),
+        "((union { float bit_cast_src; uint32_t bit_cast_dst; }){ .bit_cast_src = (x) }).bit_cast_dst"
+    );
+}
+
+#[test]
+fn test_expr_value_or() {
+    let x = Expr::new_var("x", Type::new_int32());
+    let d = Expr::new_num(8);
+    assert_eq!(Expr::value_or(x.clone(), d.clone()).to_string(), "(x) ? (x) : (0x8)");
+
+    let gnu = Expr::value_or_gnu(x, d);
+    assert_eq!(gnu.to_string(), "(x) ? (x) : (0x8)");
+
+    let mut s = Scope::new();
+    s.set_gnu_extensions().set_filename("limits.c");
+    let body = s.new_function("limit", Type::new_int32()).body();
+    body.return_expr(gnu.cast_to(Type::new_int64()));
+    assert!(s.to_string_with_defs().contains("return (int64_t)((x) ?: (0x8));"));
+}
+
+#[test]
+fn test_expr_array_len() {
+    let table = Expr::new_var("table", Type::new_uint32().to_array(16));
+    assert_eq!(Expr::array_len(table).to_string(), "(sizeof(table) / sizeof(table[0x0]))");
+
+    let inner = Expr::new_var("cfg", Type::new_struct("config")).field_access("entries");
+    assert_eq!(
+        Expr::array_len(inner).to_string(),
+        "(sizeof((cfg).entries) / sizeof((cfg).entries[0x0]))"
+    );
+}
+
+#[test]
+fn test_expr_byte_offset() {
+    let base = Expr::new_var("base", Type::new_void().to_ptr());
+    let off = Expr::new_var("off", Type::new_size());
+    let e = Expr::byte_offset(base.clone(), off, Type::new_uint32().to_ptr());
+    assert_eq!(e.to_string(), "(uint32_t *)(((char *)(base) + off))");
+    assert!(e.is_ptr());
+
+    let e = Expr::byte_offset(base, Expr::new_num(8), Type::new_struct("hdr").to_ptr());
+    assert_eq!(e.deref().to_string(), "*((struct hdr *)(((char *)(base) + 0x8)))");
+}
+
+#[test]
+fn test_expr_template_calls() {
+    let x = Expr::new_var("x", Type::new_uint64());
+    let e = Expr::template_fn_call(
+        "std::max",
+        vec![TemplateArg::Type(Type::new_uint64())],
+        vec![x.clone(), Expr::new_num(1)],
+    );
+    assert_eq!(e.to_string(), "std::max<uint64_t>(x, 0x1)");
+
+    let obj = Expr::new_var("obj", Type::new_class("Registry"));
+    let e = Expr::template_method_call(
+        &obj,
+        "get",
+        vec![TemplateArg::Type(Type::new_class("Timer")), TemplateArg::new_num(2)],
+        vec![],
+    );
+    assert_eq!(e.to_string(), "obj.get<Timer, 2>()");
+
+    let p = Expr::new_var("p", Type::new_class("Registry").to_ptr());
+    let e =
+        Expr::template_method_call(&p, "get", vec![TemplateArg::Type(Type::new_int32())], vec![x]);
+    assert_eq!(e.to_string(), "p->get<int32_t>(x)");
+
+    // calls without template arguments are unchanged
+    assert_eq!(Expr::fn_call("run", vec![]).to_string(), "run()");
+}
@@ -57,3 +57,42 @@ fn test_fields_docs() {
     f.push_doc_str("my documentation");
     assert_eq!(f.to_string(), "/// my documentation\nuint8_t my_field;\n");
 }
+
+#[test]
+fn test_fields_unnamed_bitfields() {
+    let f = Field::new_unnamed_bitfield(Type::new_uint32(), 3);
+    assert_eq!(f.to_string(), "uint32_t : 3;\n");
+
+    let f = Field::new_unnamed_bitfield(Type::new_uint32(), 0);
+    assert_eq!(f.to_string(), "uint32_t : 0;\n");
+}
+
+#[test]
+fn test_fields_trailing_comment() {
+    let mut f = Field::new("my_field", Type::new_uint8());
+    f.push_doc_str("the field").trailing_comment("note");
+    assert_eq!(f.to_string(), "/// the field\nuint8_t my_field; // note\n");
+}
+
+#[test]
+fn test_field_const_pointer_spellings() {
+    // pointer to const char
+    let mut ty = Type::new_char().to_ptr();
+    ty.const_pointee();
+    assert_eq!(Field::new("name", ty).to_string(), "const char * name;\n");
+
+    // const pointer to char
+    let mut ty = Type::new_char().to_ptr();
+    ty.const_pointer();
+    assert_eq!(Field::new("name", ty).to_string(), "char * const name;\n");
+
+    // const pointer to const char
+    let mut ty = Type::new_char();
+    ty.const_value().ptr_const();
+    assert_eq!(Field::new("name", ty).to_string(), "const char * const name;\n");
+
+    // pointer to a const pointer to char
+    let mut ty = Type::new_char().to_ptr().to_ptr();
+    ty.const_pointee();
+    assert_eq!(Field::new("names", ty).to_string(), "char * const * names;\n");
+}
@@ -0,0 +1,239 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2021, 2022 Reto Achermann (The University of British Columbia)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Function Tests
+//!
+//! This module exercises the function tests
+
+use crustal::*;
+
+#[test]
+fn test_function_noreturn_c() {
+    let mut s = Scope::new();
+    s.new_function("fatal", Type::new_void()).set_noreturn();
+    assert!(s.to_string().contains("_Noreturn void fatal(void);"));
+}
+
+#[test]
+fn test_function_noreturn_cpp() {
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp);
+    s.new_function("fatal", Type::new_void()).set_noreturn();
+    assert!(s.to_string().contains("[[noreturn]] void fatal(void);"));
+}
+
+#[test]
+fn test_function_calling_convention() {
+    let mut s = Scope::new();
+    s.new_function("callback", Type::new_void()).calling_convention("__stdcall");
+    assert!(s.to_string().contains("void __stdcall callback(void);"));
+}
+
+#[test]
+fn test_function_unnamed_params() {
+    let mut ty = Type::new_void();
+    ty.const_value().pointer();
+
+    let mut s = Scope::new();
+    let f = s.new_function("cmp", Type::new_int(32));
+    f.new_param("", ty.clone());
+    f.new_param("", ty);
+    assert!(s.to_string().contains("int32_t cmp(const void *, const void *);"));
+
+    let mut c = Class::new("Foo");
+    c.new_method("bar", Type::new_void())
+        .set_public()
+        .new_param("", Type::new_int32());
+    assert!(c.to_string().contains("void bar(int32_t);"));
+}
+
+#[test]
+fn test_function_unused_param() {
+    let mut s = Scope::new();
+    s.new_function("stub", Type::new_void())
+        .new_param("ctx", Type::new_int32())
+        .set_unused();
+    assert!(s.to_string().contains("void stub(int32_t ctx __attribute__((unused)));"));
+
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp);
+    s.new_function("stub", Type::new_void())
+        .new_param("ctx", Type::new_int32())
+        .set_unused();
+    assert!(s.to_string().contains("void stub([[maybe_unused]] int32_t ctx);"));
+}
+
+#[test]
+fn test_function_decl_only() {
+    let mut s = Scope::new();
+    s.set_filename("decl.c");
+    s.new_function_decl("ext", Type::new_int32())
+        .body()
+        .return_expr(Expr::new_num(0));
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("int32_t ext(void);"));
+    assert!(!out.contains("return"));
+}
+
+#[test]
+fn test_function_attributes() {
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp);
+    s.new_function("fast_path", Type::new_void()).push_attribute("hot");
+    assert!(s.to_string().contains("[[gnu::hot]] void fast_path(void);"));
+
+    let mut s = Scope::new();
+    s.new_function("fast_path", Type::new_void()).push_attribute("hot");
+    assert!(s.to_string().contains("__attribute__((hot)) void fast_path(void);"));
+
+    let mut attr = AttributeSpec::new_gnu("aligned");
+    attr.push_arg("16");
+    assert_eq!(attr.to_string(), "__attribute__((aligned(16)))");
+    assert_eq!(
+        AttributeSpec::new("nodiscard").to_string(),
+        "__attribute__((warn_unused_result))"
+    );
+    assert_eq!(AttributeSpec::new("maybe_unused").to_string(), "__attribute__((unused))");
+    let mut attr = AttributeSpec::new("nodiscard");
+    attr.push_arg("\"check the error\"");
+    assert_eq!(attr.to_string(), "__attribute__((warn_unused_result))");
+    // attributes without a GNU spelling require C23 and are omitted in C
+    assert!(!AttributeSpec::with_namespace("clang", "noinline").is_supported(Dialect::C));
+    assert_eq!(AttributeSpec::with_namespace("clang", "noinline").to_string(), "");
+    assert_eq!(AttributeSpec::new("no_unique_address").to_string(), "");
+
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp);
+    let f = s.new_function("check", Type::new_int32());
+    f.push_attribute_spec(AttributeSpec::new("nodiscard"));
+    f.push_attribute_spec(AttributeSpec::with_namespace("clang", "noinline"));
+    assert!(s.to_string().contains("[[nodiscard]] [[clang::noinline]] int32_t check(void);"));
+
+    let mut s = Scope::new();
+    s.new_function("check", Type::new_int32())
+        .push_attribute_spec(AttributeSpec::new("nodiscard"));
+    assert!(s
+        .to_string()
+        .contains("\n__attribute__((warn_unused_result)) int32_t check(void);"));
+}
+
+#[test]
+fn test_function_declspec() {
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Msvc);
+    s.new_export_macro("MYLIB_API", "MYLIB_BUILD");
+    s.new_function("mylib_init", Type::new_int32()).export_macro("MYLIB_API");
+    s.new_variable("mylib_version", Type::new_uint32())
+        .set_extern()
+        .declspec("dllimport");
+    s.new_class("Engine").declspec("dllexport");
+    s.new_class("Loader").export_macro("MYLIB_API");
+
+    let out = s.to_string();
+    let build = out.find("#ifdef MYLIB_BUILD").unwrap();
+    let export = out.find("#define MYLIB_API __declspec(dllexport)").unwrap();
+    let import = out.find("#define MYLIB_API __declspec(dllimport)").unwrap();
+    assert!(build < export && export < import);
+    assert!(out.contains("MYLIB_API int32_t mylib_init(void);"));
+    assert!(out.contains("extern __declspec(dllimport) uint32_t mylib_version;"));
+    assert!(out.contains("class __declspec(dllexport) Engine {"));
+    assert!(out.contains("class MYLIB_API Loader {"));
+
+    // only MSVC knows `__declspec`
+    s.set_dialect(Dialect::Cpp);
+    let out = s.to_string();
+    assert!(out.contains("\nextern uint32_t mylib_version;"));
+    assert!(out.contains("class __attribute__((visibility(\"default\"))) Engine {"));
+}
+
+#[test]
+fn test_function_main() {
+    let mut s = Scope::new();
+    s.new_main().body().return_expr(Expr::new_num(0));
+    assert!(s.to_string().contains("int main(int argc, char * * argv);"));
+
+    s.set_filename("main.c");
+    let out = s.to_string_with_defs();
+    assert!(out.contains("int main(int argc, char * * argv) {\n    return 0x0;\n}"));
+}
+
+#[test]
+fn test_function_c_wrapper() {
+    let mut f = Function::new("parse", Type::new_int32());
+    f.new_param("buf", Type::new_cstr());
+    f.new_param("len", Type::new_size());
+    f.body().return_expr(Expr::new_num(0));
+
+    let mut log = Function::new("log", Type::new_void());
+    log.new_param("msg", Type::new_cstr());
+
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp).set_filename("api.cpp");
+    s.push_function(f.c_wrapper());
+    s.push_function(log.c_wrapper());
+    assert!(s.to_string().contains("extern \"C\" int32_t parse_c(char * buf, size_t len);"));
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains(
+        "extern \"C\" int32_t parse_c(char * buf, size_t len) {\n    return parse(buf, len);\n}"
+    ));
+    assert!(out.contains("extern \"C\" void log_c(char * msg) {\n    log(msg);\n}"));
+}
+
+#[test]
+fn test_function_stmt_count() {
+    let mut f = Function::new("clamp", Type::new_int32());
+    assert!(f.body_is_empty());
+    assert_eq!(f.stmt_count(), 0);
+
+    let x = Expr::new_var("x", Type::new_int32());
+    f.body().new_comment("clamp negative values");
+    f.body()
+        .new_ifelse(&Expr::binop(x.clone(), "<", Expr::new_num(0)))
+        .then_branch()
+        .return_expr(Expr::new_num(0));
+    f.body().empty_line().return_expr(x);
+    assert!(!f.body_is_empty());
+    assert_eq!(f.stmt_count(), 3);
+}
+
+#[test]
+fn test_function_doc_skeleton() {
+    let mut f = Function::new("parse", Type::new_int32());
+    f.push_doc_str("replaced by the skeleton");
+    f.new_param("buf", Type::new_cstr()).push_doc_str("the input buffer");
+    f.new_param("len", Type::new_size());
+    f.generate_doc_skeleton();
+    assert_eq!(
+        f.to_string(),
+        "/// @brief parse\n/// \n/// @param buf the input buffer\n/// @param len\n/// @return\n\
+         int32_t parse(char * buf, size_t len);\n"
+    );
+
+    let mut f = Function::new("reset", Type::new_void());
+    f.generate_doc_skeleton();
+    assert_eq!(f.to_string(), "/// @brief reset\n/// \nvoid reset(void);\n");
+}
@@ -0,0 +1,368 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2021, 2022 Reto Achermann (The University of British Columbia)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Scope Tests
+//!
+//! This module exercises the scope tests
+
+use crustal::*;
+
+#[test]
+fn test_scope_doc() {
+    let mut s = Scope::new();
+    s.push_doc_str("my header");
+    s.new_include("stdio.h", true);
+    assert!(s.to_string().starts_with("/// my header\n"));
+}
+
+#[test]
+fn test_scope_include_groups() {
+    let mut s = Scope::new();
+    s.set_group_includes();
+    s.new_include("foo.h", false);
+    s.new_include("vector", true);
+    s.new_include("stdlib.h", true);
+    s.new_include("stdio.h", true);
+    s.new_include("string", true);
+    s.new_include("stdio.h", true);
+    assert_eq!(
+        s.to_string(),
+        "
+
+#include <stdio.h>
+#include <stdlib.h>
+
+#include <string>
+#include <vector>
+
+#include \"foo.h\""
+    );
+}
+
+#[test]
+fn test_scope_prototypes_first() {
+    let mut s = Scope::new();
+    s.set_prototypes_first().set_filename("protos.c");
+    s.new_function("foo", Type::new_void()).body().fn_call("bar", vec![]);
+    s.new_function("bar", Type::new_void()).body().return_none();
+
+    let out = s.to_string_with_defs();
+
+    let foo_decl = out.find("void foo(void);").unwrap();
+    let bar_decl = out.find("void bar(void);").unwrap();
+    let foo_def = out.find("void foo(void) {").unwrap();
+    let bar_def = out.find("void bar(void) {").unwrap();
+    assert!(foo_decl < bar_decl && bar_decl < foo_def && foo_def < bar_def);
+}
+
+fn build_deterministic_scope() -> Scope {
+    let mut s = Scope::new();
+    s.set_group_includes();
+    s.new_include("string", true);
+    s.new_include("stdio.h", true);
+    s.new_include("foo.h", false);
+    s.new_struct("point").new_field("x", Type::new_int32());
+    s.new_enum("color").new_variant("RED");
+    s.new_class("Foo").new_method("bar", Type::new_void()).set_public();
+    s.new_function("baz", Type::new_int32()).body().return_expr(Expr::new_num(0));
+    s
+}
+
+#[test]
+fn test_scope_deterministic_output() {
+    let a = build_deterministic_scope().to_string();
+    let b = build_deterministic_scope().to_string();
+    assert_eq!(a, b);
+    assert!(a.find("struct point").unwrap() < a.find("enum color").unwrap());
+    assert!(a.find("enum color").unwrap() < a.find("class Foo").unwrap());
+}
+
+#[test]
+fn test_scope_preamble() {
+    let mut s = Scope::new();
+    s.new_include("stdio.h", true);
+    s.push_preamble("#define _GNU_SOURCE");
+    assert_eq!(s.to_string(), "\n\n#define _GNU_SOURCE\n\n#include <stdio.h>");
+}
+
+#[test]
+fn test_scope_auto_includes() {
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp);
+    s.new_include("stdio.h", true);
+    s.new_struct("entry").new_field("len", Type::new_size());
+    let f = s.new_function("greet", Type::new_std_string());
+    f.new_param("count", Type::new_uint32());
+    f.body().new_variable("ok", Type::new_bool());
+    s.new_variable("name", Type::new_std_string());
+    s.auto_includes();
+    assert_eq!(
+        s.to_string(),
+        "\n\n#include <stdio.h>\n\n#include <stddef.h>\n\n#include <string>\n\n#include <stdint.h>\n\n\
+         struct entry {\n    size_t len;\n};\n\n\
+         std::string greet(uint32_t count);\n\n\
+         std::string name;"
+    );
+
+    // running the pass again does not duplicate includes
+    let out = s.to_string();
+    s.auto_includes();
+    assert_eq!(s.to_string(), out);
+}
+
+#[test]
+fn test_scope_header_banner() {
+    let mut s = Scope::new();
+    s.set_header_banner(Comment::with_str("SPDX-License-Identifier: MIT\nGenerated file"));
+    s.push_doc_str("the header");
+    s.push_preamble("#define _GNU_SOURCE");
+    s.new_include("stdio.h", true);
+
+    let out = s.to_string();
+    let mut lines = out.lines();
+    assert_eq!(lines.next(), Some("// SPDX-License-Identifier: MIT"));
+    assert_eq!(lines.next(), Some("// Generated file"));
+    assert_eq!(lines.next(), Some("/// the header"));
+}
+
+#[test]
+fn test_scope_global_array() {
+    let mut s = Scope::new();
+    s.set_filename("table.c");
+    s.new_global_array("table", Type::new_uint8(), 3)
+        .set_static()
+        .set_value(Expr::init_list(vec![Expr::new_num(1), Expr::new_num(2), Expr::new_num(3)]));
+    assert!(s.to_string().contains("static uint8_t table[3];"));
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("static uint8_t table[3] = {0x1, 0x2, 0x3};"));
+}
+
+#[test]
+fn test_scope_ifdef_split() {
+    let mut s = Scope::new();
+    s.set_filename("feature.h");
+    let ifdef = s.new_ifdef("HAVE_FEATURE");
+    ifdef
+        .then_scope()
+        .new_function("feature", Type::new_void())
+        .body()
+        .return_none();
+    ifdef
+        .other_scope()
+        .new_function("fallback", Type::new_void())
+        .body()
+        .return_none();
+
+    let hdr = s.to_string();
+    let start = hdr.find("#ifdef HAVE_FEATURE").unwrap();
+    let decl = hdr.find("void feature(void);").unwrap();
+    let other = hdr.find("#else // !HAVE_FEATURE").unwrap();
+    let fallback = hdr.find("void fallback(void);").unwrap();
+    let end = hdr.find("#endif // HAVE_FEATURE").unwrap();
+    assert!(start < decl && decl < other && other < fallback && fallback < end);
+    assert!(!hdr.contains("return;"));
+
+    s.set_filename("feature.c");
+    let src = s.to_string_with_defs();
+    let start = src.find("#ifdef HAVE_FEATURE").unwrap();
+    let def = src.find("void feature(void) {").unwrap();
+    let other = src.find("#else // !HAVE_FEATURE").unwrap();
+    let fallback = src.find("void fallback(void) {").unwrap();
+    let end = src.find("#endif // HAVE_FEATURE").unwrap();
+    assert!(start < def && def < other && other < fallback && fallback < end);
+}
+
+#[test]
+fn test_scope_generated_by() {
+    let mut s = Scope::new();
+    s.set_header_banner(Comment::with_str("SPDX-License-Identifier: MIT"));
+    s.generated_by("regen");
+    s.push_doc_str("the header");
+
+    let out = s.to_string();
+    let mut lines = out.lines();
+    assert_eq!(lines.next(), Some("// SPDX-License-Identifier: MIT"));
+    assert_eq!(lines.next(), Some("// AUTO-GENERATED by regen — DO NOT EDIT"));
+    assert_eq!(lines.next(), Some("/// the header"));
+}
+
+#[test]
+fn test_scope_inline_namespace() {
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp);
+    let ns = s.new_namespace("lib");
+    let v1 = ns.scope().new_namespace("v1");
+    v1.set_inline();
+    v1.scope().new_function("run", Type::new_void());
+
+    let out = s.to_string();
+    let lib = out.find("namespace lib {\n").unwrap();
+    let v1 = out.find("inline namespace v1 {\n").unwrap();
+    let run = out.find("void run(void);").unwrap();
+    let v1_end = out.find("} // namespace v1").unwrap();
+    let lib_end = out.find("} // namespace lib").unwrap();
+    assert!(lib < v1 && v1 < run && run < v1_end && v1_end < lib_end);
+}
+
+#[test]
+fn test_scope_to_file_creates_dirs() {
+    let root =
+        std::env::temp_dir().join(format!("crustal-test-to-file-dirs-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+
+    let mut s = Scope::new();
+    s.set_filename("include/gen/api.h");
+    s.new_function("api", Type::new_void());
+    s.to_file(&root, true).unwrap();
+    let out = std::fs::read_to_string(root.join("include/gen/api.h")).unwrap();
+    assert!(out.contains("void api(void);"));
+
+    // a file in place of the directory reports the target path
+    std::fs::write(root.join("blocked"), "").unwrap();
+    s.set_filename("blocked/api.h");
+    let err = s.to_file(&root, true).unwrap_err();
+    assert!(err.to_string().contains("api.h"));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_scope_regions() {
+    let mut s = Scope::new();
+    s.new_struct("point").new_field("x", Type::new_int32());
+    s.begin_region("helpers");
+    s.new_function("helper_a", Type::new_void());
+    s.new_function("helper_b", Type::new_void());
+    s.end_region();
+    s.new_function("api", Type::new_void());
+
+    let out = s.to_string();
+    let point = out.find("struct point").unwrap();
+    let begin = out.find("#pragma region helpers\n").unwrap();
+    let a = out.find("void helper_a(void);").unwrap();
+    let b = out.find("void helper_b(void);").unwrap();
+    let end = out.find("#pragma endregion\n").unwrap();
+    let api = out.find("void api(void);").unwrap();
+    assert!(point < begin && begin < a && a < b && b < end && end < api);
+}
+
+#[test]
+fn test_scope_crlf_line_endings() {
+    let mut s = Scope::new();
+    s.set_line_ending(LineEnding::Crlf);
+    s.new_include("stdio.h", true);
+    s.new_struct("point").new_field("x", Type::new_int32());
+    let mut body = Block::new();
+    body.return_none();
+    s.new_function("run", Type::new_void()).set_body(body);
+    s.set_filename("crlf.c");
+
+    let out = s.to_string();
+    assert_eq!(
+        out,
+        "\r\n\r\n#include <stdio.h>\r\n\r\nstruct point {\r\n    int32_t x;\r\n};\r\n\r\nvoid run(void);"
+    );
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("void run(void) {\r\n    return;\r\n}\r\n"));
+    assert!(!out.replace("\r\n", "").contains('\n'));
+}
+
+#[test]
+fn test_scope_has_include() {
+    let mut s = Scope::new();
+    s.new_include("optional", true)
+        .set_fallback(Include::new_system("experimental/optional"));
+    s.new_include("span", true).set_has_include();
+    assert_eq!(
+        s.to_string(),
+        "\n\n#if __has_include(<optional>)\n#include <optional>\n#else\n#include <experimental/optional>\n#endif\n\n\
+         #if __has_include(<span>)\n#include <span>\n#endif"
+    );
+}
+
+#[test]
+fn test_scope_symbol_manifest() {
+    let mut s = Scope::new();
+    s.set_filename("api.c");
+    s.new_struct("point").new_field("x", Type::new_int32());
+    s.new_function("area", Type::new_int32());
+    s.new_variable("origin", Type::new_struct("point"));
+    s.new_namespace("geo").scope().new_function_decl("dist", Type::new_double());
+
+    let m = s.symbol_manifest();
+    assert_eq!(m.file(), Some("api.c"));
+    assert_eq!(m.functions(), ["area", "geo::dist"]);
+    assert_eq!(m.types(), ["point"]);
+    assert_eq!(m.variables(), ["origin"]);
+
+    let json = "{\n  \"file\": \"api.c\",\n  \"functions\": [\"area\", \"geo::dist\"],\n  \
+                \"types\": [\"point\"],\n  \"variables\": [\"origin\"]\n}";
+    assert_eq!(m.to_json(), json);
+}
+
+#[test]
+fn test_scope_left_align_directives() {
+    let mut s = Scope::new();
+    let ns = s.new_namespace("hw");
+    let f = ns
+        .scope()
+        .new_ifdef("HAVE_SIMD")
+        .then_scope()
+        .new_function("sum", Type::new_void());
+    f.body().raw_no_semi("#pragma unroll");
+    f.body().fn_call("step", vec![]);
+    s.new_macro("NAME").new_arg("x").set_value("name_of(\n#x)");
+
+    s.set_filename("simd.cpp");
+
+    let out = s.to_string_with_defs();
+    assert!(out.contains("\n    #pragma unroll\n    step();\n"));
+
+    s.set_left_align_directives();
+    let out = s.to_string_with_defs();
+    assert!(out.contains("\n#ifdef HAVE_SIMD\n"));
+    assert!(out.contains("\n#pragma unroll\n    step();\n"));
+    // the stringification in the macro body is not a directive
+    assert!(out.contains("name_of(\\\n    #x)\n"));
+}
+
+#[test]
+fn test_scope_minimize_includes() {
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp);
+    s.new_struct("local").new_field("next", Type::new_struct("local").to_ptr());
+
+    let f = s.new_function("attach", Type::new_void());
+    f.new_param("dev", Type::new_class("Device").to_ptr());
+    f.new_param("cfg", Type::new_class("Config").const_ref());
+    f.new_param("hdr", Type::new_struct("hdr"));
+    s.new_function("first", Type::new_struct("hdr").to_ptr());
+    s.new_function("self_ref", Type::new_struct("local").to_ptr());
+
+    let fwd: Vec<String> = s.minimize_includes().iter().map(|t| t.to_string()).collect();
+    assert_eq!(fwd, ["Device", "Config"]);
+}
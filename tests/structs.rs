@@ -34,3 +34,58 @@ fn test_struct_forward_declaration() {
     let s = Struct::new("my_struct");
     assert_eq!(s.to_string(), "struct my_struct;\n");
 }
+
+#[test]
+fn test_struct_trailing_comments() {
+    let mut s = Struct::new("regs");
+    s.new_field("ctrl", Type::new_uint32()).trailing_comment("control register");
+    s.new_field("status_flags", Type::new_uint8()).trailing_comment("status");
+    s.new_field("x", Type::new_uint64()).trailing_comment("misc");
+    assert_eq!(
+        s.to_string(),
+        "struct regs {
+    uint32_t ctrl;         // control register
+    uint8_t status_flags;  // status
+    uint64_t x;            // misc
+};
+"
+    );
+}
+
+#[test]
+fn test_struct_offset_asserts() {
+    let mut s = Struct::new("foo");
+    s.new_field("a", Type::new_uint32());
+    s.new_field("bar", Type::new_uint32());
+
+    let asserts = s.offset_asserts(&[("a", 0), ("bar", 4)]);
+    assert_eq!(asserts.len(), 2);
+    assert_eq!(
+        asserts[1].to_string(),
+        "_Static_assert((offsetof(struct foo, bar) == 0x4), \"unexpected offset of foo.bar\");\n"
+    );
+
+    let mut scope = Scope::new();
+    scope.set_dialect(Dialect::Cpp);
+    for a in asserts {
+        scope.push_static_assert(a);
+    }
+    assert!(scope.to_string().contains(
+        "static_assert((offsetof(struct foo, a) == 0x0), \"unexpected offset of foo.a\");"
+    ));
+}
+
+#[test]
+fn test_struct_total_bitfield_bits() {
+    let mut s = Struct::new("flags");
+    s.new_field("valid", Type::new_uint32()).set_bitfield_width(1);
+    s.new_field("mode", Type::new_uint32()).set_bitfield_width(3);
+    s.push_field(Field::new_unnamed_bitfield(Type::new_uint32(), 4));
+    s.new_field("level", Type::new_uint32()).set_bitfield_width(8);
+    assert_eq!(s.total_bitfield_bits(), Some(16));
+
+    s.new_field("count", Type::new_uint32());
+    assert_eq!(s.total_bitfield_bits(), None);
+
+    assert_eq!(Struct::new("empty").total_bitfield_bits(), None);
+}
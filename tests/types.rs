@@ -90,7 +90,7 @@ fn types_base_types() {
 
     let t = Type::new(BaseType::TemplateClass(
         String::from("MyClass"),
-        vec![String::from("MyOtherClass")],
+        vec![TemplateArg::Type(Type::new_class("MyOtherClass"))],
     ));
     assert_eq!(t.to_string(), "MyClass<MyOtherClass>");
 
@@ -146,3 +146,207 @@ fn types_modifiers_deref() {
     assert!(t2.is_some());
     assert_eq!(t2.unwrap().to_string(), "const int32_t * const *");
 }
+
+#[test]
+fn types_const_qualifiers() {
+    let mut t = Type::new_int32();
+    t.pointer().const_pointee();
+    assert_eq!(t.to_string(), "const int32_t *");
+
+    let mut t = Type::new_int32();
+    t.pointer().const_pointer();
+    assert_eq!(t.to_string(), "int32_t * const");
+
+    let mut t = Type::new_int32();
+    t.const_value().pointer().const_pointer();
+    assert_eq!(t.to_string(), "const int32_t * const");
+
+    let mut t = Type::new_int32();
+    t.pointer().pointer().const_pointee();
+    assert_eq!(t.to_string(), "int32_t * const *");
+}
+
+#[test]
+fn types_references() {
+    let mut t = Type::new_class("Foo");
+    t.reference();
+    assert_eq!(t.to_string(), "Foo &");
+
+    let mut t = Type::new_class("Foo");
+    t.rvalue_reference();
+    assert_eq!(t.to_string(), "Foo &&");
+
+    let mut t = Type::new_class("Foo");
+    t.reference().reference();
+    assert_eq!(t.to_string(), "Foo &&");
+
+    let t = Type::new_class("Foo").to_ref().to_ref();
+    assert_eq!(t.to_string(), "Foo &&");
+}
+
+#[test]
+fn types_restrict() {
+    let mut t = Type::new_int32();
+    t.pointer().restrict();
+    assert_eq!(t.to_string(), "int32_t * restrict");
+
+    let mut s = Scope::new();
+    s.new_variable("buf", t.clone());
+    assert!(s.to_string().contains("int32_t * restrict buf;"));
+
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp);
+    s.new_variable("buf", t.clone());
+    assert!(s.to_string().contains("int32_t * __restrict buf;"));
+
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Msvc);
+    s.new_variable("buf", t);
+    assert!(s.to_string().contains("int32_t * __restrict buf;"));
+}
+
+#[test]
+fn types_fn_ptr() {
+    let t = Type::new_fn_ptr(Type::new_void(), vec![]);
+    assert_eq!(t.to_string(), "void (*)(void)");
+
+    let v = Variable::new("cb", t.clone());
+    assert_eq!(v.to_string(), "void (*cb)(void);\n");
+
+    let t2 = Type::new_fn_ptr(Type::new_int32(), vec![Type::new_int32(), Type::new_cstr()]);
+    let f = Field::new("handler", t2);
+    assert_eq!(f.to_string(), "int32_t (*handler)(int32_t, char *);\n");
+
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp);
+    s.new_variable("cb", t.clone());
+    assert!(s.to_string().contains("void (*cb)();"));
+
+    let mut s = Scope::new();
+    s.new_variable("cb", t);
+    assert!(s.to_string().contains("void (*cb)(void);"));
+}
+
+#[test]
+fn types_decay() {
+    let t = Type::new_int32().to_array(10);
+    let d = t.decay();
+    assert!(!d.is_array());
+    assert!(d.is_ptr());
+    assert!(d.is_same(&Type::new_int32().to_ptr()));
+    assert_eq!(d.to_string(), "int32_t *");
+
+    let t = Type::new_int32();
+    assert_eq!(t.decay().to_string(), "int32_t");
+
+    // an array field copied into a parameter becomes a pointer to its elements
+    let mut elem = Type::new_uint8();
+    elem.set_value_const();
+    let field = elem.to_array(16);
+    let mut f = Function::new("parse", Type::new_void());
+    f.new_param("buf", field.decay());
+    let param = f.to_type().decay();
+    assert_eq!(param.to_string(), "void (*)(const uint8_t *)");
+    assert!(f.to_string().contains("void parse(const uint8_t * buf);"));
+
+    let mut f = Function::new("foo", Type::new_int32());
+    f.new_param("a", Type::new_int32());
+    assert!(f.to_type().decay().is_same(&f.to_type()));
+}
+
+#[test]
+fn types_canonicalize() {
+    let mut t = Type::new_int32();
+    t.constant().constant();
+    assert_eq!(t.to_string(), "int32_t const const");
+    t.canonicalize();
+    assert_eq!(t.to_string(), "const int32_t");
+
+    let mut t = Type::new_int32();
+    t.set_value_const().constant();
+    assert_eq!(t.canonicalize().to_string(), "const int32_t");
+
+    let mut t = Type::new_int32();
+    t.pointer().volatile().constant().constant().pointer().constant();
+    t.canonicalize();
+    assert_eq!(t.to_string(), "int32_t * const volatile * const");
+}
+
+#[test]
+fn types_template_args() {
+    let t = Type::new_template(
+        "std::array",
+        vec![TemplateArg::Type(Type::new_uint8()), TemplateArg::new_num(16)],
+    );
+    assert_eq!(t.to_string(), "std::array<uint8_t, 16>");
+    assert_eq!(t.basetype().required_include(), Some("array"));
+
+    let t = Type::new_template(
+        "Pool",
+        vec![
+            TemplateArg::Type(Type::new_class("Node")),
+            TemplateArg::Value(Expr::new_var("kSize", Type::new_size())),
+            TemplateArg::Template(String::from("std::allocator")),
+        ],
+    );
+    assert_eq!(t.to_string(), "Pool<Node, kSize, std::allocator>");
+}
+
+#[test]
+fn types_ptr_const() {
+    let mut t = Type::new_int32();
+    t.set_value_const().ptr_const().pointer();
+    assert_eq!(t.to_string(), "const int32_t * const *");
+
+    let mut t = Type::new_int32();
+    t.set_value_const().ptr_const().ptr_const();
+    assert_eq!(t.to_string(), "const int32_t * const * const");
+
+    let t = Type::new_char().to_ptr().to_ptr_const();
+    assert_eq!(t.to_string(), "char * * const");
+}
+
+#[test]
+fn types_const_ref() {
+    let vec = Type::new_template("std::vector", vec![TemplateArg::Type(Type::new_plain_int())]);
+    assert_eq!(vec.const_ref().to_string(), "const std::vector<int> &");
+    assert_eq!(vec.rvalue_ref().to_string(), "std::vector<int> &&");
+
+    let mut v = Type::new_std_string();
+    v.set_value_volatile();
+    assert_eq!(v.const_ref().to_string(), "volatile const std::string &");
+
+    let p = Type::new_int32().to_ptr();
+    assert_eq!(p.const_ref().to_string(), "int32_t * const &");
+
+    // references collapse instead of producing `T & const &`
+    assert_eq!(vec.to_ref().const_ref().to_string(), "std::vector<int> &");
+    assert_eq!(vec.rvalue_ref().const_ref().to_string(), "std::vector<int> &");
+    assert_eq!(vec.const_ref().const_ref().to_string(), "const std::vector<int> &");
+}
+
+#[test]
+fn types_floating_point() {
+    assert_eq!(Type::new_float().to_string(), "float");
+    assert_eq!(Type::new_double().to_string(), "double");
+    assert_eq!(Type::new_long_double().to_string(), "long double");
+    assert_eq!(Type::new_double().to_ptr().to_string(), "double *");
+}
+
+#[test]
+fn types_char_spellings() {
+    assert_eq!(Type::new_char().to_string(), "char");
+    assert_eq!(Type::new_signed_char().to_string(), "signed char");
+    assert_eq!(Type::new_unsigned_char().to_string(), "unsigned char");
+    assert_eq!(Type::new(BaseType::UnsignedChar).to_ptr().to_string(), "unsigned char *");
+    assert!(Type::new_signed_char().is_integer());
+    assert!(!Type::new_unsigned_char().is_same(&Type::new_char()));
+}
+
+#[test]
+fn types_plain_int() {
+    assert_eq!(Type::new_plain_int().to_string(), "int");
+    assert!(Type::new_plain_int().is_integer());
+    assert!(!Type::new_plain_int().is_same(&Type::new_int32()));
+    assert_eq!(Type::new_plain_int().basetype().required_include(), None);
+}
@@ -0,0 +1,68 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2021, 2022 Reto Achermann (The University of British Columbia)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Union Tests
+//!
+//! This module exercises the union tests
+
+use crustal::*;
+
+#[test]
+fn test_tagged_union() {
+    let mut u = TaggedUnion::new("shape");
+    u.new_variant("circle", Type::new_struct("circle"));
+    u.new_variant("rect", Type::new_struct("rect"));
+
+    assert_eq!(
+        u.to_string(),
+        "enum shape_kind {
+    SHAPE_CIRCLE,
+    SHAPE_RECT
+};
+struct shape {
+    enum shape_kind kind;
+    union {
+        struct circle circle;
+        struct rect rect;
+    };
+};
+"
+    );
+}
+
+#[test]
+fn test_union_size_asserts() {
+    let mut u = Union::new("value");
+    u.new_field("word", Type::new_uint64());
+    u.new_field("bytes", Type::new_uint8().to_array(8));
+
+    let asserts = u.size_asserts();
+    assert_eq!(asserts.len(), 2);
+    assert_eq!(
+        asserts[0].to_string(),
+        "_Static_assert((sizeof(union value) >= sizeof(uint64_t)), \"union value is smaller than member word\");\n"
+    );
+    assert!(asserts[1].to_string().contains("(sizeof(union value) >= sizeof(uint8_t[8]))"));
+}
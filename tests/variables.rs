@@ -0,0 +1,72 @@
+// C/C++ Code Generator For Rust
+//
+//
+// MIT License
+//
+// Copyright (c) 2021, 2022 Reto Achermann (The University of British Columbia)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! # Variable Tests
+//!
+//! This module exercises the variable tests
+
+use crustal::*;
+
+#[test]
+fn test_variable_trailing_comment() {
+    let mut v = Variable::new("counter", Type::new_uint32());
+    v.trailing_comment("number of calls");
+    assert_eq!(v.to_string(), "uint32_t counter; // number of calls\n");
+}
+
+#[test]
+fn test_variable_zero_init() {
+    let mut b = Block::new();
+    b.new_variable("p", Type::new_struct("point")).zero_init();
+    b.new_variable("buf", Type::new_uint8().to_array(4)).zero_init();
+    assert_eq!(b.to_string(), "struct point p = {0};\nuint8_t buf[4] = {0};\n");
+
+    let mut s = Scope::new();
+    s.set_dialect(Dialect::Cpp);
+    s.new_variable("origin", Type::new_struct("point")).zero_init();
+    assert!(s.to_string_with_defs().contains("struct point origin = {};"));
+}
+
+#[test]
+fn test_variable_const_pointer_spellings() {
+    let mut ty = Type::new_char().to_ptr();
+    ty.const_pointee();
+    assert_eq!(Variable::new("msg", ty).to_string(), "const char * msg;\n");
+
+    let mut ty = Type::new_char().to_ptr();
+    ty.const_pointer();
+    assert_eq!(Variable::new("buf", ty).to_string(), "char * const buf;\n");
+
+    let mut ty = Type::new_char();
+    ty.const_value().ptr_const();
+    let mut v = Variable::new("greeting", ty);
+    v.set_static().set_value(Expr::new_str("hi"));
+    assert_eq!(v.to_string(), "static const char * const greeting;\n");
+
+    // the name stays inside the declarator of function pointers
+    let mut ty = Type::new_fn_ptr(Type::new_void(), vec![]);
+    ty.const_pointer();
+    assert_eq!(Variable::new("cb", ty).to_string(), "void (* const cb)(void);\n");
+}